@@ -0,0 +1,73 @@
+use matrix::elimination::Eliminate;
+use matrix::pivoting::Pivoting;
+use matrix::Matrix;
+
+///Reduces the leading `columns` columns of an augmented matrix to reduced row echelon form, a column at a time,
+/// carrying the columns after them along. Returns the column of each pivot, in order.
+///
+/// Each pivot is chosen by Eliminate::select_pivot within its column, so no columns are swapped.
+/// A column whose chosen pivot is zero or `negligible` has no pivot, and its candidates are set to zero.
+///### Arguments
+/// * `work`: the augmented matrix, such as [A | B | I], reduced in place.
+/// * `columns`: the number of leading columns, those of A, to find pivots in.
+/// * `pivoting`: Partial, ScaledPartial or FirstNonZero. The others search beyond the column, and are taken as Partial.
+/// * `negligible`: whether a candidate pivot is too small to use, for a tolerance. Zero is always too small.
+/// * `simd`: scales and reduces rows with the vectorized kernels. See matrix::kernels.
+pub(crate) fn reduce_to_echelon<T: Eliminate>(
+    work: &mut Matrix<T>,
+    columns: usize,
+    pivoting: Pivoting,
+    negligible: impl Fn(&T) -> bool,
+    simd: bool,
+) -> Vec<usize> {
+    let m = work.rows();
+    let pivoting = match pivoting {
+        Pivoting::ScaledPartial | Pivoting::FirstNonZero => pivoting,
+        _ => Pivoting::Partial,
+    };
+    //Reciprocals of the largest absolute value in each row of A, for scaled partial pivoting. They follow their rows.
+    let mut scales = vec![T::one(); m];
+    if pivoting == Pivoting::ScaledPartial {
+        let mut leading = Matrix::new(m, columns);
+        for (row, values) in work.values.iter().enumerate() {
+            leading.values[row] = values[..columns].to_vec();
+        }
+        scales = T::row_scales(&leading);
+    }
+
+    let mut pivot_columns = Vec::new();
+    '_main: for column in 0..columns {
+        let step = pivot_columns.len();
+        if step == m {
+            break '_main;
+        }
+        let rows: Vec<usize> = (step..m).collect();
+        let row = match T::select_pivot(work, &rows, &[column], pivoting, &scales) {
+            Some((row, _)) if !negligible(&work.values[row][column]) => row,
+            _ => {
+                //A negligible column: clear it, so that the result is exactly in echelon form.
+                for row in rows {
+                    work.values[row][column] = T::zero();
+                }
+                continue '_main;
+            }
+        };
+        work.swap_rows((row, step));
+        scales.swap(row, step);
+        let pivot_inverse = T::one() / work.values[step][column].clone();
+        T::scale(&mut work.values[step], &pivot_inverse, simd);
+        work.values[step][column] = T::one();
+
+        let pivot_row = work.values[step].clone();
+        '_traverse_matrix: for (row, values) in work.values.iter_mut().enumerate() {
+            if row == step || values[column] == T::zero() {
+                continue;
+            }
+            let factor = values[column].clone();
+            T::subtract_multiple(values, &factor, &pivot_row, simd);
+            values[column] = T::zero();
+        }
+        pivot_columns.push(column);
+    }
+    pivot_columns
+}
@@ -0,0 +1,153 @@
+use crate::echelon::reduce_to_echelon;
+use crate::Error;
+use matrix::bit_matrix::BitMatrix;
+use matrix::elimination::Eliminate;
+use matrix::pivoting::Pivoting;
+use matrix::Matrix;
+
+///The result of gauss_jordan_field and gauss_jordan_gf2, for a matrix type M.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldReduction<M> {
+    ///The number of pivots, the rank of A.
+    pub rank: usize,
+    ///The column of A holding each pivot, in order.
+    pub pivot_columns: Vec<usize>,
+    ///The inverse of A, when A is square and of full rank.
+    pub inverse: Option<M>,
+    ///A solution X of A * X = B, when every column of B is consistent with A. Free variables are zero.
+    pub solution: Option<M>,
+}
+
+///Gauss-Jordan elimination over any field, such as the integers modulo a prime, PrimeField.
+///
+///### Usage
+/// Reduces [A | B | I] to reduced row echelon form. There is no rounding error in a finite field, and no order to
+/// choose a largest pivot by, so each pivot is the first non-zero element in its column. A Scalar, such as f64,
+/// takes the largest element of the column instead, by partial pivoting. A may be m x n and of any rank.
+/// ```
+///    use gauss_jordan_elimination::gauss_jordan_field;
+///    use matrix::field::PrimeField;
+///    type F5 = PrimeField<5>;
+///    let a = matrix::Matrix::from(vec![vec![F5::new(1), F5::new(2)], vec![F5::new(3), F5::new(4)]]);
+///    let b = matrix::Matrix::from(vec![vec![F5::new(1)], vec![F5::new(0)]]);
+///    let reduction = gauss_jordan_field(&a, &b).unwrap();
+///    assert_eq!(reduction.rank, 2);
+///    //x = [-2, 3/2] = [3, 4] modulo 5.
+///    assert_eq!(reduction.solution.unwrap().values, vec![vec![F5::new(3)], vec![F5::new(4)]]);
+///    let inverse = reduction.inverse.unwrap();
+///    assert_eq!(inverse.values[0], vec![F5::new(3), F5::new(1)]);
+/// ```
+///### Errors
+/// `DimensionMismatch` if A and B have different numbers of rows. A singular A is not an error: its rank is reported.
+pub fn gauss_jordan_field<F: Eliminate>(
+    a: &Matrix<F>,
+    b: &Matrix<F>,
+) -> Result<FieldReduction<Matrix<F>>, Error> {
+    let (m, n, k) = (a.rows(), a.columns(), b.columns());
+    if b.rows() != m {
+        let (a_rows, b_rows) = (m, b.rows());
+        return Err(Error::DimensionMismatch { a_rows, b_rows });
+    }
+    let square = m == n;
+    let mut augmented = Matrix::new(m, n + k + if square { m } else { 0 });
+    for (row, values) in augmented.values.iter_mut().enumerate() {
+        values[..n].clone_from_slice(&a.values[row]);
+        values[n..n + k].clone_from_slice(&b.values[row]);
+        if square {
+            values[n + k + row] = F::one();
+        }
+    }
+
+    let pivot_columns = reduce_to_echelon(&mut augmented, n, Pivoting::Partial, |_| false, false);
+
+    let rank = pivot_columns.len();
+    let zero = F::zero();
+    let consistent = augmented.values[rank..]
+        .iter()
+        .all(|values| values[n..n + k].iter().all(|value| *value == zero));
+    let solution = consistent.then(|| {
+        let mut solution = Matrix::new(n, k);
+        for (row, column) in pivot_columns.iter().enumerate() {
+            solution.values[*column] = augmented.values[row][n..n + k].to_vec();
+        }
+        solution
+    });
+    let inverse = (square && rank == n).then(|| {
+        let mut inverse = Matrix::new(n, n);
+        for (row, values) in augmented.values.iter().enumerate() {
+            inverse.values[row] = values[n + k..].to_vec();
+        }
+        inverse
+    });
+    Ok(FieldReduction {
+        rank,
+        pivot_columns,
+        inverse,
+        solution,
+    })
+}
+
+///Gauss-Jordan elimination over GF(2), on bit packed rows. See gauss_jordan_field.
+///
+///### Usage
+/// Every non-zero element is 1, so a pivot row needs no scaling, and eliminating with it is an exclusive or of words.
+/// ```
+///    use gauss_jordan_elimination::gauss_jordan_gf2;
+///    use matrix::bit_matrix::BitMatrix;
+///    let a = BitMatrix::from(vec![vec![1, 1, 0], vec![0, 1, 1], vec![1, 0, 1]]);
+///    let b = BitMatrix::from(vec![vec![1], vec![1], vec![0]]);
+///    let reduction = gauss_jordan_gf2(&a, &b).unwrap();
+///    //The third row is the sum of the first two.
+///    assert_eq!(reduction.rank, 2);
+///    assert_eq!(reduction.inverse, None);
+///    assert_eq!(reduction.solution.unwrap(), BitMatrix::from(vec![vec![0], vec![1], vec![0]]));
+/// ```
+///### Errors
+/// `DimensionMismatch` if A and B have different numbers of rows.
+pub fn gauss_jordan_gf2(a: &BitMatrix, b: &BitMatrix) -> Result<FieldReduction<BitMatrix>, Error> {
+    let (m, n, k) = (a.rows(), a.columns(), b.columns());
+    if b.rows() != m {
+        let (a_rows, b_rows) = (m, b.rows());
+        return Err(Error::DimensionMismatch { a_rows, b_rows });
+    }
+    let square = m == n;
+    let mut augmented = a.augment(b);
+    if square {
+        augmented = augmented.augment(&BitMatrix::identity(m));
+    }
+
+    let mut pivot_columns = Vec::new();
+    '_main: for column in 0..n {
+        let step = pivot_columns.len();
+        let pivot_row = match (step..m).find(|row| augmented.get(*row, column)) {
+            Some(row) => row,
+            None => continue '_main,
+        };
+        augmented.swap_rows((pivot_row, step));
+        '_traverse_matrix: for row in 0..m {
+            if row != step && augmented.get(row, column) {
+                augmented.add_row(step, row);
+            }
+        }
+        pivot_columns.push(column);
+    }
+
+    let rank = pivot_columns.len();
+    let consistent = (rank..m).all(|row| (n..n + k).all(|column| !augmented.get(row, column)));
+    let solution = consistent.then(|| {
+        let mut solution = BitMatrix::new(n, k);
+        for (row, pivot_column) in pivot_columns.iter().enumerate() {
+            for column in 0..k {
+                solution.set(*pivot_column, column, augmented.get(row, n + column));
+            }
+        }
+        solution
+    });
+    let inverse = (square && rank == n).then(|| augmented.columns_range(n + k, n + k + n));
+    Ok(FieldReduction {
+        rank,
+        pivot_columns,
+        inverse,
+        solution,
+    })
+}
@@ -1,26 +1,123 @@
-use matrix;
+pub use matrix::elimination::Error;
+use matrix::elimination::{validate, Eliminate};
+use matrix::pivoting::{Pivoting, Pivots};
+use matrix::trace::{NoTrace, Step, Trace};
+
+mod echelon;
+mod field;
+pub use field::{gauss_jordan_field, gauss_jordan_gf2, FieldReduction};
+mod rref;
+pub use rref::{Consistency, ReducedRowEchelon, RowEchelonOptions};
+
+///The optimizations gauss_jordan may apply. Each flag guards the code it enables with an if statement
+///of the same name, so a word search for the flag finds every line it affects.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GaussJordanOptions {
+    ///Builds the inverse of A in place of A, instead of reducing A to the identity.
+    /// Saves passing an n x n identity in B to recover the inverse.
+    pub return_inverse_in_a: bool,
+    ///How each pivot is chosen. Complete pivoting, the choice of the book, searches every row and column not yet pivoted.
+    pub pivoting: Pivoting,
+    ///The number of threads the rows are reduced on, once the pivot row is scaled. Each row is reduced exactly
+    /// as on one thread, so the result is the same. Rows reduced on more than one thread are not reported to the trace.
+    pub threads: usize,
+    ///The smallest n for which the rows are reduced on `threads` threads. The threads are started at every step,
+    /// which costs more than the reduction of a smaller matrix.
+    pub parallel_threshold: usize,
+    ///Scales and reduces rows with vectorized kernels. The results are identical. Defaults to off, like the
+    /// other options structs. See matrix::kernels.
+    pub simd: bool,
+}
+
+impl Default for GaussJordanOptions {
+    fn default() -> Self {
+        GaussJordanOptions {
+            return_inverse_in_a: true,
+            pivoting: Pivoting::Complete,
+            threads: 1,
+            parallel_threshold: 512,
+            simd: false,
+        }
+    }
+}
+
 ///Linear equation solution by Gauss-Jordan elimination, equation (2.1.1). The input matrix
 ///is a[0..n-1][0..n-1]. b[0..n-1][0..m-1] is input containing the m right-hand side vectors.
 ///On output, a is replaced by its matrix inverse, and b is replaced by the corresponding set of
 ///solution vectors.
-pub fn gauss_jordan(
-    a: &mut crate::matrix::Matrix,
-    b: &mut crate::matrix::Matrix,
+///
+///### Errors
+/// `NotSquare`, `DimensionMismatch` and `NonFiniteInput` are returned before anything is written, leaving a and b untouched.
+/// `SingularMatrix` is returned part way through the elimination. a and b are then left holding the system as
+/// reduced by the steps before `step`, with rows swapped and scaled. Keep a copy of a and b if they are needed afterwards.
+pub fn gauss_jordan<T: Eliminate>(
+    a: &mut matrix::Matrix<T>,
+    b: &mut matrix::Matrix<T>,
     return_inverse_in_a: bool,
-) {
+) -> Result<(), Error> {
+    let options = GaussJordanOptions {
+        return_inverse_in_a,
+        ..GaussJordanOptions::default()
+    };
+    gauss_jordan_with(a, b, options, &mut NoTrace)?;
+    Ok(())
+}
+
+///Performs gauss_jordan with the given optimizations, reporting each pivot selection, row swap, scaling and elimination to `trace`.
+///
+/// Returns where each pivot was found. The pivot of step k was found at (rows[k], columns[k]),
+/// and its row was then swapped with row columns[k] to put it on the diagonal.
+/// A may hold any field that implements Eliminate, such as PrimeField, which chooses its own pivots.
+///### Usage
+/// ```
+///    use gauss_jordan_elimination::{gauss_jordan_with, GaussJordanOptions};
+///    use matrix::trace::{Recorder, Step};
+///    let mut a = matrix::Matrix::from(vec![vec![3.0, -2.0], vec![6.0, 4.0]]);
+///    let mut b = matrix::Matrix::from(vec![vec![4.0], vec![-8.0]]);
+///    let mut recorder = Recorder::new();
+///    gauss_jordan_with(&mut a, &mut b, GaussJordanOptions::default(), &mut recorder).unwrap();
+///    assert_eq!(recorder.steps()[0], Step::SelectPivot { row: 1, column: 0 });
+///    assert_eq!(recorder.frames().last().unwrap().after.b, Some(b));
+/// ```
+pub fn gauss_jordan_with<T: Eliminate>(
+    a: &mut matrix::Matrix<T>,
+    b: &mut matrix::Matrix<T>,
+    options: GaussJordanOptions,
+    trace: &mut impl Trace<T>,
+) -> Result<Pivots, Error> {
+    validate(a, b)?;
+    let return_inverse_in_a = options.return_inverse_in_a;
+    trace.start(a, Some(b));
     let n = a.rows();
-    let m = b.columns();
     //This usize vector is used for bookkeeping on the pivoting.
     let mut ipiv = vec![0; a.columns()];
-    let mut swaps: Vec<(usize, usize)> = Vec::new();
+    let mut pivots = Pivots::default();
+    //Reciprocals of the largest absolute value in each row, for scaled partial pivoting. They follow their rows when swapped.
+    let mut scales = vec![T::one(); n];
+    if options.pivoting == Pivoting::ScaledPartial {
+        scales = T::row_scales(a);
+    }
 
     //This is the main loop over the columns to be reduced.
     '_main: for step in 0..a.columns() {
-        let (mut irow, mut icol): (usize, usize) = (0, 0);
-
-        dbg!(step, &a.values);
-        select_pivot_location(&a, (&mut irow, &mut icol), &mut ipiv);
+        //Candidates lie in the rows and columns not yet pivoted. A pivot's row is swapped to the row of its column.
+        let unpivoted: Vec<usize> = (0..a.columns()).filter(|index| ipiv[*index] == 0).collect();
+        let (irow, icol) =
+            match T::select_pivot(a, &unpivoted, &unpivoted, options.pivoting, &scales) {
+                Some(location) => location,
+                None => return Err(Error::SingularMatrix { step }),
+            };
         ipiv[icol] += 1;
+        pivots.rows.push(irow);
+        pivots.columns.push(icol);
+        trace.record(
+            Step::SelectPivot {
+                row: irow,
+                column: icol,
+            },
+            a,
+            Some(b),
+        );
 
         // For the pivot element If element is at (0, 2), move it to (0,0) by swapping row0 with row2.
         // If element is on the diagonal, e.g.(row == col) then return doing nothing.
@@ -28,40 +125,57 @@ pub fn gauss_jordan(
         if irow != icol {
             a.swap_rows((irow, icol));
             b.swap_rows((irow, icol));
+            scales.swap(irow, icol);
+            trace.record(Step::SwapRows { rows: (irow, icol) }, a, Some(b));
         }
 
-        assert_ne!(a.values[icol][icol], 0.0, "gauss_jordan: Singular Matrix");
         /*We are now ready to divide the pivot row by the pivot element, located at irow and icol.*/
         let pivot_index = icol; //Code after this point assumes the pivot row is equal to the pivot column,
                                 //and the pivot element is on the diagonal,
-        let pivot_inverse = 1.0 / a.values[pivot_index][pivot_index];
+        let pivot_inverse = T::one() / a.values[pivot_index][pivot_index].clone();
 
         //Multiplicatively Scale the pivot_row by the pivot element.
         if return_inverse_in_a {
-            swaps.push((irow, icol));
-            a.values[pivot_index][pivot_index] = 1.0; //1.0 <-Set pivot point to 1 before scaling and reduction.
+            a.values[pivot_index][pivot_index] = T::one(); //1.0 <-Set pivot point to 1 before scaling and reduction.
         }
 
-        a.scale_row(pivot_index, pivot_inverse);
-        b.scale_row(pivot_index, pivot_inverse);
+        T::scale(&mut a.values[pivot_index], &pivot_inverse, options.simd);
+        T::scale(&mut b.values[pivot_index], &pivot_inverse, options.simd);
+        trace.record(
+            Step::ScaleRow {
+                row: pivot_index,
+                scale: pivot_inverse,
+            },
+            a,
+            Some(b),
+        );
 
+        let pivot_row = (a.values[pivot_index].clone(), b.values[pivot_index].clone());
+        let parallel = options.threads > 1 && n >= options.parallel_threshold;
+        if parallel {
+            reduce_in_parallel(a, b, &pivot_row, pivot_index, options);
+            continue '_main;
+        }
         '_traverse_matrix: for row in 0..n {
             //Next, we reduce the rows... //...except for the pivot one, of course.
             if row == pivot_index {
                 continue;
             }
-
-            let linear_combination_constant: f32 = a.values[row][pivot_index]; //Get element in the same columns as the pivot element.
-            if return_inverse_in_a {
-                a.values[row][pivot_index] = 0.0;
-            } // <- set pivot column
-
-            '_traverse_elements_in_row: for col in 0..n {
-                a.values[row][col] -= a.values[pivot_index][col] * linear_combination_constant;
-            }
-            for col in 0..m {
-                b.values[row][col] -= b.values[pivot_index][col] * linear_combination_constant;
-            }
+            let linear_combination_constant = reduce_row(
+                (&mut a.values[row], &mut b.values[row]),
+                &pivot_row,
+                pivot_index,
+                options,
+            );
+            trace.record(
+                Step::EliminateRow {
+                    target: row,
+                    source: pivot_index,
+                    factor: linear_combination_constant,
+                },
+                a,
+                Some(b),
+            );
         }
     }
     //THE STEPS THAT RETURN INVERSE IN A
@@ -69,38 +183,65 @@ pub fn gauss_jordan(
     // Set the rest of pivot column to 0.00 just before replacing each row with a linear combination.(sbove)
     // Apply swap row operations in reverse order as swap column operations to bring out Matrix A^-1 (below)
     if return_inverse_in_a {
-        for (row, column) in swaps.iter().rev() {
+        for (row, column) in pivots.rows.iter().zip(&pivots.columns).rev() {
             if row == column {
                 continue;
             }
             a.swap_columns((*row, *column));
+            trace.record(
+                Step::SwapColumns {
+                    columns: (*row, *column),
+                },
+                a,
+                Some(b),
+            );
         }
     }
+    Ok(pivots)
 }
 
-fn select_pivot_location(
-    a: &crate::matrix::Matrix,
-    (row_of_max, col_of_max): (&mut usize, &mut usize),
-    ipiv: &mut Vec<u32>,
+///Subtracts the multiple of the scaled pivot row that zeroes the pivot column of `row`, in A and B.
+/// Returns the multiple.
+fn reduce_row<T: Eliminate>(
+    row: (&mut [T], &mut [T]),
+    pivot_row: &(Vec<T>, Vec<T>),
+    pivot_index: usize,
+    options: GaussJordanOptions,
+) -> T {
+    let linear_combination_constant: T = row.0[pivot_index].clone(); //Get element in the same columns as the pivot element.
+    if options.return_inverse_in_a {
+        row.0[pivot_index] = T::zero();
+    } // <- set pivot column
+
+    let (constant, simd) = (&linear_combination_constant, options.simd);
+    T::subtract_multiple(row.0, constant, &pivot_row.0, simd);
+    T::subtract_multiple(row.1, constant, &pivot_row.1, simd);
+    linear_combination_constant
+}
+
+///Reduces every row but the pivot row, splitting the rows of A and B into one block per thread.
+fn reduce_in_parallel<T: Eliminate>(
+    a: &mut matrix::Matrix<T>,
+    b: &mut matrix::Matrix<T>,
+    pivot_row: &(Vec<T>, Vec<T>),
+    pivot_index: usize,
+    options: GaussJordanOptions,
 ) {
-    let mut max_absolute_value = 0.0_f32.abs();
-    '_traverse_matrix: for row in 0..a.rows() {
-        if ipiv[row] == 1 {
-            continue;
-        }
-        'traverse_remaining_columns: for column in 0..a.columns() {
-            assert!(ipiv[column] <= 1, "Interesting: see select_pivot_location");
-            if ipiv[column] != 0 {
-                continue;
-            }
-            dbg!(a.values[row][column].abs(), row, column);
-            if a.values[row][column].abs() <= max_absolute_value {
-                continue 'traverse_remaining_columns;
-            }
-            //record location of new max
-            max_absolute_value = a.values[row][column].abs();
-            *row_of_max = row;
-            *col_of_max = column;
+    let rows_per_thread = a.rows().div_ceil(options.threads);
+    let a_blocks = a.values.chunks_mut(rows_per_thread);
+    let b_blocks = b.values.chunks_mut(rows_per_thread);
+    std::thread::scope(|scope| {
+        '_traverse_blocks: for (block, (a_rows, b_rows)) in a_blocks.zip(b_blocks).enumerate() {
+            scope.spawn(move || {
+                let first_row = block * rows_per_thread;
+                for (offset, row) in a_rows.iter_mut().zip(b_rows.iter_mut()).enumerate() {
+                    if first_row + offset == pivot_index {
+                        continue;
+                    }
+                    let row = (row.0.as_mut_slice(), row.1.as_mut_slice());
+                    reduce_row(row, pivot_row, pivot_index, options);
+                }
+            });
         }
-    }
+    });
 }
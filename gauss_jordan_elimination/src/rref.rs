@@ -0,0 +1,207 @@
+use crate::echelon::reduce_to_echelon;
+use matrix::pivoting::Pivoting;
+use matrix::{Matrix, Scalar};
+
+///The choices ReducedRowEchelon may make.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RowEchelonOptions {
+    ///How each pivot is chosen within its column: Partial, the default, ScaledPartial or FirstNonZero.
+    /// The others search beyond the column, and are taken as Partial.
+    pub pivoting: Pivoting,
+    ///Scales and reduces rows with vectorized kernels. The results are identical. Defaults to off. See matrix::kernels.
+    pub simd: bool,
+}
+
+impl Default for RowEchelonOptions {
+    fn default() -> Self {
+        RowEchelonOptions {
+            pivoting: Pivoting::Partial,
+            simd: false,
+        }
+    }
+}
+
+///The reduced row echelon form R of an m x n matrix A, computed to a tolerance, and the bases it reveals.
+///
+///### Usage
+/// Reduces [A | I] by Gauss-Jordan elimination, column by column, with partial pivoting unless RowEchelonOptions says otherwise. A column whose remaining
+/// candidates are all within the tolerance of zero has no pivot, and they are set to zero. The row operations
+/// are kept as E, with E * A = R, so that any right hand side can be classified afterwards.
+/// ```
+///    use gauss_jordan_elimination::{Consistency, ReducedRowEchelon};
+///    let a = matrix::Matrix::from(vec![vec![1.0, 2.0, 3.0], vec![2.0, 4.0, 7.0]]);
+///    let rref = ReducedRowEchelon::new(&a, None);
+///    assert_eq!(rref.rank(), 2);
+///    assert_eq!(rref.pivot_columns(), &[0, 2]);
+///    assert_eq!(rref.null_space().values, vec![vec![-2.0], vec![1.0], vec![0.0]]);
+///    let b = matrix::Matrix::from(vec![vec![1.0], vec![3.0]]);
+///    assert!(matches!(rref.classify(&b), Consistency::Infinite { .. }));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReducedRowEchelon<T = f32> {
+    a: Matrix<T>,
+    reduced: Matrix<T>,
+    operations: Matrix<T>,
+    pivot_columns: Vec<usize>,
+    tolerance: T,
+}
+
+///Whether A * X = B has a solution, found by ReducedRowEchelon::classify.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Consistency<T = f32> {
+    ///Every column of A has a pivot, and B lies in the column space of A.
+    Unique { solution: Matrix<T> },
+    ///B lies in the column space of A, and the `free_columns` without a pivot may take any value.
+    /// The particular solution sets them to zero. Add any combination of the null space to it.
+    Infinite {
+        particular: Matrix<T>,
+        free_columns: Vec<usize>,
+    },
+    ///B does not lie in the column space of A: some row of E * B is not zero where R is.
+    Inconsistent,
+}
+
+impl<T: Scalar> ReducedRowEchelon<T> {
+    ///Reduces A. Candidates no larger than `tolerance` in absolute value are treated as zero.
+    /// None takes max(m, n) * epsilon * ||A||_1. MATLAB's rref takes the infinity norm in its place.
+    /// Exact types, such as Rational, have an epsilon of zero, so only zero is treated as zero.
+    pub fn new(a: &Matrix<T>, tolerance: Option<T>) -> Self {
+        ReducedRowEchelon::with_options(a, tolerance, RowEchelonOptions::default())
+    }
+
+    ///Performs new with the given choices.
+    pub fn with_options(a: &Matrix<T>, tolerance: Option<T>, options: RowEchelonOptions) -> Self {
+        let (m, n) = (a.rows(), a.columns());
+        let tolerance =
+            tolerance.unwrap_or_else(|| T::from_f64(m.max(n) as f64) * T::epsilon() * a.norm_one());
+        let mut work = Matrix::new(m, n + m);
+        for (row, values) in work.values.iter_mut().enumerate() {
+            values[..n].clone_from_slice(&a.values[row]);
+            values[n + row] = T::one();
+        }
+
+        let negligible = |value: &T| value.abs() <= tolerance;
+        let pivot_columns =
+            reduce_to_echelon(&mut work, n, options.pivoting, negligible, options.simd);
+
+        let split = |range: std::ops::Range<usize>| {
+            let mut part = Matrix::new(m, range.len());
+            for (row, values) in work.values.iter().enumerate() {
+                part.values[row] = values[range.clone()].to_vec();
+            }
+            part
+        };
+        ReducedRowEchelon {
+            a: a.clone(),
+            reduced: split(0..n),
+            operations: split(n..n + m),
+            pivot_columns,
+            tolerance,
+        }
+    }
+
+    ///The reduced row echelon form R.
+    pub fn reduced(&self) -> &Matrix<T> {
+        &self.reduced
+    }
+
+    ///The row operations E, an invertible m x m matrix with E * A = R.
+    pub fn operations(&self) -> &Matrix<T> {
+        &self.operations
+    }
+
+    ///The column of A holding each pivot, in order.
+    pub fn pivot_columns(&self) -> &[usize] {
+        &self.pivot_columns
+    }
+
+    ///The numerical rank, the number of pivots.
+    pub fn rank(&self) -> usize {
+        self.pivot_columns.len()
+    }
+
+    ///The tolerance the pivots were compared against.
+    pub fn tolerance(&self) -> &T {
+        &self.tolerance
+    }
+
+    ///The columns of A without a pivot, the free variables of A * X = B.
+    pub fn free_columns(&self) -> Vec<usize> {
+        (0..self.a.columns())
+            .filter(|column| !self.pivot_columns.contains(column))
+            .collect()
+    }
+
+    ///A basis of the null space of A, one n x 1 column for each free column, as an n x (n - rank) matrix.
+    /// Each basis vector has a one in its free column, zeros in the other free columns,
+    /// and the negated column of R in the pivot columns.
+    pub fn null_space(&self) -> Matrix<T> {
+        let free_columns = self.free_columns();
+        let mut basis = Matrix::new(self.a.columns(), free_columns.len());
+        for (vector, free) in free_columns.iter().enumerate() {
+            basis.values[*free][vector] = T::one();
+            for (row, pivot) in self.pivot_columns.iter().enumerate() {
+                basis.values[*pivot][vector] = -self.reduced.values[row][*free].clone();
+            }
+        }
+        basis
+    }
+
+    ///A basis of the column space of A: the columns of A that hold pivots, as an m x rank matrix.
+    pub fn column_space(&self) -> Matrix<T> {
+        let mut basis = Matrix::new(self.a.rows(), self.rank());
+        for (vector, column) in self.pivot_columns.iter().enumerate() {
+            for row in 0..self.a.rows() {
+                basis.values[row][vector] = self.a.values[row][*column].clone();
+            }
+        }
+        basis
+    }
+
+    ///Classifies A * X = B as having a unique solution, infinitely many, or none, and returns a solution if there is one.
+    /// The rows of E * B below the rank must be zero for a solution to exist, to within the tolerance,
+    /// or max(m, n) * epsilon * ||B||_1 if that is larger. Panics if B has the wrong number of rows.
+    pub fn classify(&self, b: &Matrix<T>) -> Consistency<T> {
+        let (m, n, k) = (self.a.rows(), self.a.columns(), b.columns());
+        assert_eq!(
+            b.rows(),
+            m,
+            "Right hand side has {} rows. Expected {}.",
+            b.rows(),
+            m
+        );
+        let mut transformed = Matrix::new(m, k);
+        for row in 0..m {
+            for column in 0..k {
+                let mut sum = T::zero();
+                for i in 0..m {
+                    sum += self.operations.values[row][i].clone() * b.values[i][column].clone();
+                }
+                transformed.values[row][column] = sum;
+            }
+        }
+        let b_tolerance = T::from_f64(m.max(n) as f64) * T::epsilon() * b.norm_one();
+        let tolerance = match b_tolerance > self.tolerance {
+            true => b_tolerance,
+            false => self.tolerance.clone(),
+        };
+        let consistent = transformed.values[self.rank()..]
+            .iter()
+            .flatten()
+            .all(|value| value.abs() <= tolerance);
+        if !consistent {
+            return Consistency::Inconsistent;
+        }
+        let mut solution = Matrix::new(n, k);
+        for (row, pivot) in self.pivot_columns.iter().enumerate() {
+            solution.values[*pivot] = transformed.values[row].clone();
+        }
+        match self.rank() == n {
+            true => Consistency::Unique { solution },
+            false => Consistency::Infinite {
+                particular: solution,
+                free_columns: self.free_columns(),
+            },
+        }
+    }
+}
@@ -6,7 +6,7 @@ fn return_solution_in_place_of_b() {
     let mut matrix_b = matrix::Matrix::from(vec![vec![4.0], vec![-8.0]]);
     let solution = matrix::Matrix::from(vec![vec![0.0], vec![-2.0]]);
 
-    gauss_jordan(&mut matrix, &mut matrix_b, false);
+    gauss_jordan(&mut matrix, &mut matrix_b, false).unwrap();
 
     assert_eq!(matrix_b, solution);
 }
@@ -20,6 +20,820 @@ fn return_inverse_in_place_of_a() {
         vec![4.0 / 24.0, 2.0 / 24.0],
         vec![-6.0 / 24.0, 3.0 / 24.0],
     ]);
-    gauss_jordan(&mut matrix_a, &mut matrix_b, true); //set inverse flag to true.
+    gauss_jordan(&mut matrix_a, &mut matrix_b, true).unwrap(); //set inverse flag to true.
     assert_eq!(matrix_a, solution);
 }
+#[test]
+fn return_singular_matrix_at_step() {
+    use gauss_jordan_elimination::{gauss_jordan, Error};
+    use matrix;
+    let mut matrix_a = matrix::Matrix::from(vec![
+        vec![1.0, 2.0, 3.0],
+        vec![2.0, 4.0, 6.0],
+        vec![1.0, 0.0, 1.0],
+    ]);
+    let mut matrix_b = matrix::Matrix::from(vec![vec![1.0], vec![2.0], vec![3.0]]);
+    let result = gauss_jordan(&mut matrix_a, &mut matrix_b, false);
+    assert_eq!(result, Err(Error::SingularMatrix { step: 2 }));
+}
+#[test]
+fn return_dimension_mismatch_without_modifying_inputs() {
+    use gauss_jordan_elimination::{gauss_jordan, Error};
+    use matrix;
+    let mut matrix_a = matrix::Matrix::from(vec![vec![3.0, -2.0], vec![6.0, 4.0]]);
+    let mut matrix_b = matrix::Matrix::from(vec![vec![4.0], vec![-8.0], vec![1.0]]);
+    let (original_a, original_b) = (matrix_a.clone(), matrix_b.clone());
+    let result = gauss_jordan(&mut matrix_a, &mut matrix_b, true);
+    assert_eq!(
+        result,
+        Err(Error::DimensionMismatch {
+            a_rows: 2,
+            b_rows: 3
+        })
+    );
+    assert_eq!(matrix_a, original_a);
+    assert_eq!(matrix_b, original_b);
+}
+#[test]
+fn return_not_square() {
+    use gauss_jordan_elimination::{gauss_jordan, Error};
+    use matrix;
+    let mut matrix_a = matrix::Matrix::from(vec![vec![3.0, -2.0, 1.0], vec![6.0, 4.0, 1.0]]);
+    let mut matrix_b = matrix::Matrix::from(vec![vec![4.0], vec![-8.0]]);
+    let result = gauss_jordan(&mut matrix_a, &mut matrix_b, false);
+    assert_eq!(
+        result,
+        Err(Error::NotSquare {
+            rows: 2,
+            columns: 3
+        })
+    );
+}
+#[test]
+fn return_non_finite_input() {
+    use gauss_jordan_elimination::{gauss_jordan, Error};
+    use matrix;
+    let mut matrix_a = matrix::Matrix::from(vec![vec![3.0, -2.0], vec![6.0, 4.0]]);
+    let mut matrix_b = matrix::Matrix::from(vec![vec![f32::NAN], vec![-8.0]]);
+    let result = gauss_jordan(&mut matrix_a, &mut matrix_b, false);
+    assert_eq!(result, Err(Error::NonFiniteInput));
+}
+#[test]
+fn trace_records_each_step() {
+    use gauss_jordan_elimination::{gauss_jordan, gauss_jordan_with, GaussJordanOptions};
+    use matrix::trace::{Recorder, Step};
+    let mut matrix_a = matrix::Matrix::from(vec![vec![3.0, -2.0], vec![6.0, 4.0]]);
+    let mut matrix_b = matrix::Matrix::from(vec![vec![4.0], vec![-8.0]]);
+    let (mut untraced_a, mut untraced_b) = (matrix_a.clone(), matrix_b.clone());
+    let mut recorder = Recorder::new();
+    let options = GaussJordanOptions::default();
+    gauss_jordan_with(&mut matrix_a, &mut matrix_b, options, &mut recorder).unwrap();
+    gauss_jordan(&mut untraced_a, &mut untraced_b, true).unwrap();
+    assert_eq!((&matrix_a, &matrix_b), (&untraced_a, &untraced_b));
+
+    assert_eq!(
+        recorder.steps(),
+        vec![
+            Step::SelectPivot { row: 1, column: 0 },
+            Step::SwapRows { rows: (1, 0) },
+            Step::ScaleRow {
+                row: 0,
+                scale: 1.0 / 6.0
+            },
+            Step::EliminateRow {
+                target: 1,
+                source: 0,
+                factor: 3.0
+            },
+            Step::SelectPivot { row: 1, column: 1 },
+            Step::ScaleRow {
+                row: 1,
+                scale: -1.0 / 4.0
+            },
+            Step::EliminateRow {
+                target: 0,
+                source: 1,
+                factor: 2.0 / 3.0
+            },
+            Step::SwapColumns { columns: (1, 0) },
+        ]
+    );
+    //Each frame starts where the previous one ended.
+    let frames = recorder.frames();
+    assert_eq!(
+        frames[0].before.a,
+        matrix::Matrix::from(vec![vec![3.0, -2.0], vec![6.0, 4.0]])
+    );
+    for pair in frames.windows(2) {
+        assert_eq!(pair[0].after, pair[1].before);
+    }
+    assert_eq!(frames.last().unwrap().after.a, matrix_a);
+}
+#[test]
+fn return_inverse_in_a_is_optional() {
+    use gauss_jordan_elimination::{gauss_jordan_with, GaussJordanOptions};
+    use matrix::trace::NoTrace;
+    let original_a = matrix::Matrix::from(vec![vec![3.0, -2.0], vec![6.0, 4.0]]);
+    let inverse = matrix::Matrix::from(vec![
+        vec![4.0 / 24.0, 2.0 / 24.0],
+        vec![-6.0 / 24.0, 3.0 / 24.0],
+    ]);
+
+    let (mut a, mut b) = (original_a.clone(), matrix::Matrix::identity(2));
+    let options = GaussJordanOptions {
+        return_inverse_in_a: false,
+        ..GaussJordanOptions::default()
+    };
+    gauss_jordan_with(&mut a, &mut b, options, &mut NoTrace).unwrap();
+    assert_eq!(a, matrix::Matrix::identity(2));
+    assert_eq!(b, inverse);
+
+    let (mut a, mut b) = (original_a, matrix::Matrix::new(2, 0));
+    gauss_jordan_with(&mut a, &mut b, GaussJordanOptions::default(), &mut NoTrace).unwrap();
+    assert_eq!(a, inverse);
+}
+#[test]
+fn gauss_jordan_costs_about_three_times_lu_decomposition() {
+    use gauss_jordan_elimination::{gauss_jordan_with, GaussJordanOptions};
+    use matrix::count::{count_operations, Counted};
+    use matrix::trace::NoTrace;
+    let n = 30;
+    let mut a = matrix::Matrix::new(n, n);
+    for row in 0..n {
+        for column in 0..n {
+            a.values[row][column] = Counted(((row * 7 + column * 3) % 11) as f64);
+        }
+        a.values[row][row] = Counted(100.0);
+    }
+    let b = matrix::Matrix::from(vec![vec![Counted(1.0)]; n]);
+
+    //Reduce A to the identity, as Numerical Recipes counts it, rather than building the inverse in place.
+    let options = GaussJordanOptions {
+        return_inverse_in_a: false,
+        ..GaussJordanOptions::default()
+    };
+    let (_, gauss_jordan_counts) = count_operations(|| {
+        let (mut a, mut b) = (a.clone(), b.clone());
+        gauss_jordan_with(&mut a, &mut b, options, &mut NoTrace).unwrap();
+    });
+    let (_, lu_counts) = count_operations(|| {
+        let (mut a, mut b) = (a.clone(), b.clone());
+        let (permutation, _) = lu_decomposition::decompose(&mut a).unwrap();
+        lu_decomposition::crout(&a, &permutation, &mut b);
+    });
+
+    //N^3 against N^3 / 3 multiplications, less lower order terms.
+    let ratio = gauss_jordan_counts.multiplications as f64 / lu_counts.multiplications as f64;
+    assert!((2.5..3.5).contains(&ratio), "ratio was {ratio}");
+    assert!(gauss_jordan_counts.flops() > lu_counts.flops());
+}
+#[test]
+fn every_pivoting_strategy_inverts() {
+    use gauss_jordan_elimination::{gauss_jordan_with, GaussJordanOptions};
+    use matrix::pivoting::Pivoting;
+    use matrix::trace::NoTrace;
+    let original_a: matrix::Matrix<f64> = matrix::Matrix::from(vec![
+        vec![1.0, 5.0, 0.0],
+        vec![2.0, 3.0, 9.0],
+        vec![4.0, 6.0, 1.0],
+    ]);
+    //b = A * [1, 2, 3]
+    let original_b = matrix::Matrix::from(vec![vec![11.0], vec![35.0], vec![19.0]]);
+    let mut inverses = Vec::new();
+    for pivoting in [
+        Pivoting::None,
+        Pivoting::Partial,
+        Pivoting::ScaledPartial,
+        Pivoting::Complete,
+        Pivoting::Rook,
+    ] {
+        let (mut a, mut b) = (original_a.clone(), original_b.clone());
+        let options = GaussJordanOptions {
+            pivoting,
+            ..GaussJordanOptions::default()
+        };
+        let pivots = gauss_jordan_with(&mut a, &mut b, options, &mut NoTrace).unwrap();
+        for (row, expected) in [1.0, 2.0, 3.0].iter().enumerate() {
+            assert!(
+                (b.values[row][0] - expected).abs() < 1e-12,
+                "{:?}",
+                pivoting
+            );
+        }
+        if let Pivoting::None | Pivoting::Partial | Pivoting::ScaledPartial = pivoting {
+            assert_eq!(pivots.columns, vec![0, 1, 2], "{:?}", pivoting);
+        }
+        inverses.push(a);
+    }
+    for inverse in &inverses[1..] {
+        for row in 0..3 {
+            for column in 0..3 {
+                let error = inverse.values[row][column] - inverses[0].values[row][column];
+                assert!(error.abs() < 1e-12);
+            }
+        }
+    }
+}
+#[test]
+fn pivoting_strategies_record_where_each_pivot_was_found() {
+    use gauss_jordan_elimination::{gauss_jordan_with, GaussJordanOptions};
+    use matrix::pivoting::{Pivoting, Pivots};
+    use matrix::trace::NoTrace;
+    let original_a = matrix::Matrix::from(vec![
+        vec![1.0, 5.0, 0.0],
+        vec![2.0, 3.0, 9.0],
+        vec![4.0, 6.0, 1.0],
+    ]);
+    let pivots = |pivoting| {
+        let (mut a, mut b) = (original_a.clone(), matrix::Matrix::new(3, 0));
+        let options = GaussJordanOptions {
+            pivoting,
+            ..GaussJordanOptions::default()
+        };
+        gauss_jordan_with(&mut a, &mut b, options, &mut NoTrace).unwrap()
+    };
+    assert_eq!(
+        pivots(Pivoting::None),
+        Pivots {
+            rows: vec![0, 1, 2],
+            columns: vec![0, 1, 2]
+        }
+    );
+    assert_eq!(pivots(Pivoting::Complete).rows[0], 1);
+    assert_eq!(pivots(Pivoting::Complete).columns[0], 2);
+    assert_eq!(pivots(Pivoting::Rook).rows[0], 2);
+    assert_eq!(pivots(Pivoting::Rook).columns[0], 1);
+}
+#[test]
+fn complete_pivoting_compares_the_most() {
+    use gauss_jordan_elimination::{gauss_jordan_with, GaussJordanOptions};
+    use matrix::count::{count_operations, Counted};
+    use matrix::pivoting::Pivoting;
+    use matrix::trace::NoTrace;
+    let n = 12;
+    let mut a = matrix::Matrix::new(n, n);
+    for row in 0..n {
+        for column in 0..n {
+            a.values[row][column] = Counted(((row * 7 + column * 3) % 11) as f64 + 1.0);
+        }
+        a.values[row][row] = Counted(100.0);
+    }
+    let comparisons = |pivoting| {
+        let options = GaussJordanOptions {
+            pivoting,
+            ..GaussJordanOptions::default()
+        };
+        let (_, counts) = count_operations(|| {
+            let (mut a, mut b) = (a.clone(), matrix::Matrix::new(n, 0));
+            gauss_jordan_with(&mut a, &mut b, options, &mut NoTrace).unwrap();
+        });
+        counts.comparisons
+    };
+    let none = comparisons(Pivoting::None);
+    let partial = comparisons(Pivoting::Partial);
+    let rook = comparisons(Pivoting::Rook);
+    let complete = comparisons(Pivoting::Complete);
+    assert!(none < partial, "{} {}", none, partial);
+    assert!(partial < rook, "{} {}", partial, rook);
+    assert!(rook < complete, "{} {}", rook, complete);
+}
+
+#[test]
+fn parallel_reduction_gives_the_serial_result() {
+    use gauss_jordan_elimination::{gauss_jordan_with, GaussJordanOptions};
+    use matrix::trace::{Recorder, Step};
+    let n = 40;
+    let mut state = 17u64;
+    let mut random = |rows, columns| {
+        let mut matrix: matrix::Matrix<f64> = matrix::Matrix::new(rows, columns);
+        for row in 0..rows {
+            for column in 0..columns {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                matrix.values[row][column] = (state >> 11) as f64 / (1u64 << 52) as f64 - 1.0;
+            }
+        }
+        matrix
+    };
+    let (a, b) = (random(n, n), random(n, 3));
+    for return_inverse_in_a in [true, false] {
+        let solve = |threads, parallel_threshold| {
+            let (mut a, mut b) = (a.clone(), b.clone());
+            let options = GaussJordanOptions {
+                return_inverse_in_a,
+                threads,
+                parallel_threshold,
+                ..GaussJordanOptions::default()
+            };
+            let mut recorder = Recorder::new();
+            let pivots = gauss_jordan_with(&mut a, &mut b, options, &mut recorder).unwrap();
+            let eliminations = recorder
+                .steps()
+                .iter()
+                .filter(|step| matches!(step, Step::EliminateRow { .. }))
+                .count();
+            (a, b, pivots, eliminations)
+        };
+        let (serial_a, serial_b, serial_pivots, eliminations) = solve(1, 0);
+        assert_eq!(eliminations, n * (n - 1));
+        for threads in [2, 3, 8, 64] {
+            let (parallel_a, parallel_b, parallel_pivots, eliminations) = solve(threads, 0);
+            assert_eq!(parallel_a, serial_a, "{} threads", threads);
+            assert_eq!(parallel_b, serial_b, "{} threads", threads);
+            assert_eq!(parallel_pivots, serial_pivots);
+            assert_eq!(eliminations, 0);
+        }
+        //Below the threshold the rows are reduced serially, and each reduction is traced.
+        let (below_a, _, _, eliminations) = solve(8, n + 1);
+        assert_eq!(below_a, serial_a);
+        assert_eq!(eliminations, n * (n - 1));
+    }
+}
+
+#[test]
+fn simd_row_operations_give_identical_results() {
+    use gauss_jordan_elimination::{gauss_jordan_with, GaussJordanOptions};
+    use matrix::trace::NoTrace;
+    let n = 21;
+    let mut a: matrix::Matrix<f64> = matrix::Matrix::new(n, n);
+    let mut b: matrix::Matrix<f64> = matrix::Matrix::new(n, 2);
+    for row in 0..n {
+        for column in 0..n {
+            a.values[row][column] = ((row * 13 + column * 7) % 17) as f64 / 3.0 - 2.5;
+        }
+        a.values[row][row] += 9.0;
+        b.values[row] = vec![row as f64, 1.0];
+    }
+    let solve = |simd| {
+        let (mut a, mut b) = (a.clone(), b.clone());
+        let options = GaussJordanOptions {
+            simd,
+            ..GaussJordanOptions::default()
+        };
+        gauss_jordan_with(&mut a, &mut b, options, &mut NoTrace).unwrap();
+        (a, b)
+    };
+    assert_eq!(solve(true), solve(false));
+}
+
+#[test]
+fn rational_inverse_is_exact() {
+    use gauss_jordan_elimination::gauss_jordan;
+    use matrix::rational::Rational;
+    use matrix::{Field, Matrix};
+    let n = 6;
+    let hilbert = Matrix::from(
+        (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| Rational::new(1, (i + j + 1) as i64))
+                    .collect()
+            })
+            .collect(),
+    );
+    let mut inverse = hilbert.clone();
+    let mut b = Matrix::<Rational>::new(n, 1);
+    gauss_jordan(&mut inverse, &mut b, true).unwrap();
+    //The inverse of a Hilbert matrix has integer elements.
+    assert_eq!(inverse.values[0][0], Rational::from(36));
+    assert_eq!(inverse.values[5][5], Rational::from(698544));
+    assert!(inverse
+        .values
+        .iter()
+        .flatten()
+        .all(|value| value.is_integer()));
+    for i in 0..n {
+        for j in 0..n {
+            let mut sum = Rational::zero();
+            for k in 0..n {
+                sum += hilbert.values[i][k].clone() * inverse.values[k][j].clone();
+            }
+            let expected = if i == j {
+                Rational::one()
+            } else {
+                Rational::zero()
+            };
+            assert_eq!(sum, expected);
+        }
+    }
+}
+
+#[test]
+fn dual_numbers_differentiate_the_inverse() {
+    use gauss_jordan_elimination::gauss_jordan;
+    use matrix::dual::Dual;
+    use matrix::Matrix;
+    //A(t) = [[2 + t, 1], [1, 3]]. d(A^-1)/dt = -A^-1 * dA/dt * A^-1.
+    let mut a = Matrix::from(vec![
+        vec![Dual::variable(2.0), Dual::constant(1.0)],
+        vec![Dual::constant(1.0), Dual::constant(3.0)],
+    ]);
+    let mut b = Matrix::from(vec![vec![Dual::constant(3.0)], vec![Dual::constant(5.0)]]);
+    gauss_jordan(&mut a, &mut b, true).unwrap();
+    let inverse = [[0.6, -0.2], [-0.2, 0.4]];
+    for row in 0..2 {
+        for column in 0..2 {
+            let expected = -inverse[row][0] * inverse[0][column];
+            assert!((a.values[row][column].value - inverse[row][column]).abs() < 1e-15);
+            assert!((a.values[row][column].derivative - expected).abs() < 1e-15);
+        }
+    }
+    //x = [0.8, 1.4], dx/dt = -A^-1 * [x0, 0].
+    assert!((b.values[0][0].derivative + 0.6 * 0.8).abs() < 1e-15);
+    assert!((b.values[1][0].derivative - 0.2 * 0.8).abs() < 1e-15);
+}
+
+///Returns the product of two matrices over a field.
+fn field_product<F: matrix::Field>(
+    a: &matrix::Matrix<F>,
+    b: &matrix::Matrix<F>,
+) -> matrix::Matrix<F> {
+    let mut product = matrix::Matrix::new(a.rows(), b.columns());
+    for row in 0..a.rows() {
+        for column in 0..b.columns() {
+            for k in 0..a.columns() {
+                product.values[row][column] +=
+                    a.values[row][k].clone() * b.values[k][column].clone();
+            }
+        }
+    }
+    product
+}
+
+///Returns `rows` x `columns` values below `modulus`.
+fn random_residues(rows: usize, columns: usize, modulus: u64, seed: u64) -> Vec<Vec<u64>> {
+    let mut state = seed;
+    (0..rows)
+        .map(|_| {
+            (0..columns)
+                .map(|_| {
+                    state = state
+                        .wrapping_mul(6364136223846793005)
+                        .wrapping_add(1442695040888963407);
+                    (state >> 11) % modulus
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn prime_field_inverse_and_solution() {
+    use gauss_jordan_elimination::gauss_jordan_field;
+    use matrix::field::PrimeField;
+    use matrix::Matrix;
+    type F = PrimeField<1_000_000_007>;
+    let residues = |rows, columns, seed| {
+        let values = random_residues(rows, columns, 1_000_000_007, seed);
+        Matrix::from(
+            values
+                .into_iter()
+                .map(|row| row.into_iter().map(F::new).collect())
+                .collect(),
+        )
+    };
+    let (a, b) = (residues(12, 12, 1), residues(12, 3, 2));
+    let reduction = gauss_jordan_field(&a, &b).unwrap();
+    assert_eq!(reduction.rank, 12);
+    assert_eq!(reduction.pivot_columns, (0..12).collect::<Vec<_>>());
+    let inverse = reduction.inverse.unwrap();
+    assert_eq!(field_product(&a, &inverse), Matrix::identity(12));
+    assert_eq!(field_product(&a, &reduction.solution.unwrap()), b);
+}
+
+#[test]
+fn gauss_jordan_reduces_over_a_prime_field() {
+    use gauss_jordan_elimination::{gauss_jordan_field, gauss_jordan_with, GaussJordanOptions};
+    use matrix::field::PrimeField;
+    use matrix::pivoting::Pivoting;
+    use matrix::trace::NoTrace;
+    use matrix::Matrix;
+    type F = PrimeField<1_000_000_007>;
+    let residues = |rows, columns, seed| {
+        let values = random_residues(rows, columns, 1_000_000_007, seed);
+        Matrix::from(
+            values
+                .into_iter()
+                .map(|row| row.into_iter().map(F::new).collect())
+                .collect(),
+        )
+    };
+    let (a, b) = (residues(9, 9, 3), residues(9, 2, 4));
+    let reduction = gauss_jordan_field(&a, &b).unwrap();
+    //Every strategy takes the first non-zero candidate in a field with no order.
+    for pivoting in [
+        Pivoting::Partial,
+        Pivoting::ScaledPartial,
+        Pivoting::Complete,
+    ] {
+        let (mut inverse, mut solution) = (a.clone(), b.clone());
+        let options = GaussJordanOptions {
+            pivoting,
+            ..GaussJordanOptions::default()
+        };
+        gauss_jordan_with(&mut inverse, &mut solution, options, &mut NoTrace).unwrap();
+        assert_eq!(Some(inverse), reduction.inverse);
+        assert_eq!(Some(solution), reduction.solution);
+    }
+}
+
+#[test]
+fn prime_field_rank_deficient_systems() {
+    use gauss_jordan_elimination::{gauss_jordan_field, Error};
+    use matrix::field::PrimeField;
+    use matrix::Matrix;
+    type F7 = PrimeField<7>;
+    let matrix = |values: Vec<Vec<i64>>| {
+        Matrix::from(
+            values
+                .into_iter()
+                .map(|row| row.into_iter().map(F7::from_signed).collect())
+                .collect(),
+        )
+    };
+    //The third row is twice the first plus the second, modulo 7. The second column is zero,
+    //and the third is twice the first, since 4 * 2 = 1 modulo 7.
+    let a = matrix(vec![vec![1, 0, 2, 3], vec![4, 0, 1, 1], vec![6, 0, 5, 0]]);
+    let consistent = matrix(vec![vec![1], vec![2], vec![4]]);
+    let reduction = gauss_jordan_field(&a, &consistent).unwrap();
+    assert_eq!(
+        (reduction.rank, reduction.pivot_columns.clone()),
+        (2, vec![0, 3])
+    );
+    assert_eq!(reduction.inverse, None);
+    let solution = reduction.solution.unwrap();
+    assert_eq!(field_product(&a, &solution), consistent);
+    assert_eq!(solution.values[1], vec![F7::new(0)]);
+    assert_eq!(solution.values[2], vec![F7::new(0)]);
+
+    let inconsistent = matrix(vec![vec![1], vec![2], vec![5]]);
+    assert_eq!(
+        gauss_jordan_field(&a, &inconsistent).unwrap().solution,
+        None
+    );
+    assert_eq!(
+        gauss_jordan_field(&a, &matrix(vec![vec![1]])).unwrap_err(),
+        Error::DimensionMismatch {
+            a_rows: 3,
+            b_rows: 1
+        }
+    );
+}
+
+#[test]
+fn prime_field_empty_system() {
+    use gauss_jordan_elimination::gauss_jordan_field;
+    use matrix::field::PrimeField;
+    use matrix::Matrix;
+    type F7 = PrimeField<7>;
+    let reduction = gauss_jordan_field(&Matrix::<F7>::new(0, 0), &Matrix::new(0, 0)).unwrap();
+    assert_eq!((reduction.rank, reduction.pivot_columns), (0, vec![]));
+    assert_eq!(reduction.inverse.map(|inverse| inverse.rows()), Some(0));
+    assert_eq!(reduction.solution.map(|solution| solution.rows()), Some(0));
+}
+
+#[test]
+fn bit_packed_gf2_agrees_with_the_prime_field_of_two() {
+    use gauss_jordan_elimination::{gauss_jordan_field, gauss_jordan_gf2};
+    use matrix::bit_matrix::BitMatrix;
+    use matrix::field::PrimeField;
+    use matrix::Matrix;
+    type F2 = PrimeField<2>;
+    //Wider than one word, so that rows span several.
+    for (n, seed) in [(5, 1), (70, 2), (130, 3), (130, 4)] {
+        let values = random_residues(n, n + 2, 2, seed);
+        let a_values: Vec<Vec<u64>> = values.iter().map(|row| row[..n].to_vec()).collect();
+        let b_values: Vec<Vec<u64>> = values.iter().map(|row| row[n..].to_vec()).collect();
+        let bits = |values: &Vec<Vec<u64>>| {
+            BitMatrix::from(
+                values
+                    .iter()
+                    .map(|row| row.iter().map(|v| *v as u8).collect())
+                    .collect(),
+            )
+        };
+        let field = |values: &Vec<Vec<u64>>| {
+            Matrix::from(
+                values
+                    .iter()
+                    .map(|row| row.iter().map(|v| F2::new(*v)).collect())
+                    .collect(),
+            )
+        };
+        let packed = gauss_jordan_gf2(&bits(&a_values), &bits(&b_values)).unwrap();
+        let general = gauss_jordan_field(&field(&a_values), &field(&b_values)).unwrap();
+        assert_eq!(packed.rank, general.rank);
+        assert_eq!(packed.pivot_columns, general.pivot_columns);
+        let unpack = |matrix: &BitMatrix| {
+            Matrix::from(
+                (0..matrix.rows())
+                    .map(|row| {
+                        matrix
+                            .row(row)
+                            .into_iter()
+                            .map(|v| F2::new(v as u64))
+                            .collect()
+                    })
+                    .collect(),
+            )
+        };
+        assert_eq!(packed.inverse.as_ref().map(unpack), general.inverse);
+        assert_eq!(packed.solution.as_ref().map(unpack), general.solution);
+        if let Some(inverse) = general.inverse {
+            assert_eq!(
+                field_product(&field(&a_values), &inverse),
+                Matrix::identity(n)
+            );
+        }
+    }
+}
+#[test]
+fn rref_reveals_numerical_rank_and_bases() {
+    use gauss_jordan_elimination::{ReducedRowEchelon, RowEchelonOptions};
+    use matrix::pivoting::Pivoting;
+    //The third column is the sum of the first two and the fourth row is the sum of the first two, up to noise.
+    let noise = 1e-13;
+    let a = matrix::Matrix::from(vec![
+        vec![1.0, 2.0, 3.0 + noise, 4.0],
+        vec![2.0, -1.0, 1.0, 0.5],
+        vec![0.0, 3.0, 3.0, -2.0],
+        vec![3.0, 1.0, 4.0 - noise, 4.5],
+    ]);
+    let rref = ReducedRowEchelon::new(&a, Some(1e-9f64));
+    assert_eq!(rref.rank(), 3);
+    assert_eq!(rref.pivot_columns(), &[0, 1, 3]);
+    assert_eq!(rref.free_columns(), vec![2]);
+    //The default tolerance sees the noise and reports full rank.
+    assert_eq!(ReducedRowEchelon::new(&a, None).rank(), 4);
+
+    let reduced = rref.reduced();
+    for (row, pivot) in rref.pivot_columns().iter().enumerate() {
+        assert_eq!(reduced.values[row][*pivot], 1.0);
+    }
+    assert!(reduced.values[3].iter().all(|value| *value == 0.0));
+
+    let null_space = rref.null_space();
+    assert_eq!((null_space.rows(), null_space.columns()), (4, 1));
+    for row in 0..4 {
+        let product: f64 = (0..4)
+            .map(|i| a.values[row][i] * null_space.values[i][0])
+            .sum();
+        assert!(product.abs() < 1e-9, "A * null = {}", product);
+    }
+    let column_space = rref.column_space();
+    assert_eq!((column_space.rows(), column_space.columns()), (4, 3));
+    assert_eq!(column_space.values[2], vec![0.0, 3.0, -2.0]);
+
+    //The row order of the pivots may change, but not the rank or the bases.
+    for (pivoting, simd) in [(Pivoting::ScaledPartial, false), (Pivoting::Partial, true)] {
+        let options = RowEchelonOptions { pivoting, simd };
+        let other = ReducedRowEchelon::with_options(&a, Some(1e-9f64), options);
+        assert_eq!(other.pivot_columns(), rref.pivot_columns());
+        assert_eq!(other.column_space(), column_space);
+        let difference = other.null_space().values[2][0] - null_space.values[2][0];
+        assert!(difference.abs() < 1e-12);
+    }
+}
+#[test]
+fn rref_classifies_right_hand_sides() {
+    use gauss_jordan_elimination::{Consistency, ReducedRowEchelon, RowEchelonOptions};
+    use matrix::pivoting::Pivoting;
+    let product = |a: &matrix::Matrix<f64>, x: &matrix::Matrix<f64>| -> Vec<f64> {
+        (0..a.rows())
+            .map(|row| {
+                (0..a.columns())
+                    .map(|i| a.values[row][i] * x.values[i][0])
+                    .sum()
+            })
+            .collect()
+    };
+
+    //Tall and of full column rank: unique when b is in the column space, inconsistent otherwise.
+    let tall = matrix::Matrix::from(vec![vec![1.0f64, 1.0], vec![1.0, 2.0], vec![1.0, 3.0]]);
+    let rref = ReducedRowEchelon::new(&tall, None);
+    assert_eq!(rref.null_space().columns(), 0);
+    let b = matrix::Matrix::from(vec![vec![3.0], vec![5.0], vec![7.0]]);
+    match rref.classify(&b) {
+        Consistency::Unique { solution } => {
+            assert!((solution.values[0][0] - 1.0).abs() < 1e-12);
+            assert!((solution.values[1][0] - 2.0).abs() < 1e-12);
+        }
+        other => panic!("Expected a unique solution, got {:?}", other),
+    }
+    let b = matrix::Matrix::from(vec![vec![3.0], vec![5.0], vec![8.0]]);
+    assert_eq!(rref.classify(&b), Consistency::Inconsistent);
+
+    //Wide: infinitely many, and the particular solution plus any null space vector solves it.
+    let wide = matrix::Matrix::from(vec![vec![1.0, 2.0, 1.0, -1.0], vec![2.0, 4.0, 0.0, 2.0]]);
+    let rref = ReducedRowEchelon::new(&wide, None);
+    let b = matrix::Matrix::from(vec![vec![1.0], vec![6.0]]);
+    let (particular, free_columns) = match rref.classify(&b) {
+        Consistency::Infinite {
+            particular,
+            free_columns,
+        } => (particular, free_columns),
+        other => panic!("Expected infinitely many solutions, got {:?}", other),
+    };
+    assert_eq!(free_columns, vec![1, 3]);
+    assert_eq!(product(&wide, &particular), vec![1.0, 6.0]);
+    let null_space = rref.null_space();
+    let mut shifted = particular.clone();
+    for row in 0..4 {
+        shifted.values[row][0] += 2.0 * null_space.values[row][0] - 3.0 * null_space.values[row][1];
+    }
+    let residual = product(&wide, &shifted);
+    assert!((residual[0] - 1.0).abs() < 1e-12 && (residual[1] - 6.0).abs() < 1e-12);
+
+    //A zero matrix has rank zero; only b = 0 is consistent.
+    let zero = matrix::Matrix::<f64>::new(2, 3);
+    let rref = ReducedRowEchelon::new(&zero, None);
+    assert_eq!(rref.rank(), 0);
+    assert_eq!(rref.column_space().columns(), 0);
+    assert_eq!(
+        rref.null_space().values,
+        matrix::Matrix::<f64>::identity(3).values
+    );
+    let b = matrix::Matrix::from(vec![vec![0.0], vec![1.0]]);
+    assert_eq!(rref.classify(&b), Consistency::Inconsistent);
+
+    //No rows at all, under each pivoting.
+    for pivoting in [Pivoting::Partial, Pivoting::ScaledPartial] {
+        let options = RowEchelonOptions {
+            pivoting,
+            ..RowEchelonOptions::default()
+        };
+        let rref =
+            ReducedRowEchelon::with_options(&matrix::Matrix::<f64>::new(0, 0), None, options);
+        assert_eq!(rref.rank(), 0);
+        assert_eq!(rref.operations().rows(), 0);
+    }
+}
+#[test]
+fn rref_is_exact_for_rationals() {
+    use gauss_jordan_elimination::{Consistency, ReducedRowEchelon};
+    use matrix::rational::Rational;
+    use matrix::Field;
+    let r = |n: i64, d: i64| Rational::new(n, d);
+    //Rank 2: the third row is the first plus half the second.
+    let a = matrix::Matrix::from(vec![
+        vec![r(1, 3), r(1, 2), r(1, 1)],
+        vec![r(2, 1), r(2, 3), r(0, 1)],
+        vec![r(4, 3), r(5, 6), r(1, 1)],
+    ]);
+    let rref = ReducedRowEchelon::new(&a, None);
+    assert_eq!(*rref.tolerance(), Rational::zero());
+    assert_eq!(rref.rank(), 2);
+    assert_eq!(rref.pivot_columns(), &[0, 1]);
+    let null_space = rref.null_space();
+    for row in 0..3 {
+        let mut sum = Rational::zero();
+        for i in 0..3 {
+            sum += a.values[row][i].clone() * null_space.values[i][0].clone();
+        }
+        assert_eq!(sum, Rational::zero());
+    }
+    let b = matrix::Matrix::from(vec![vec![r(1, 1)], vec![r(1, 1)], vec![r(3, 2)]]);
+    assert!(matches!(rref.classify(&b), Consistency::Infinite { .. }));
+    //Off by the smallest amount, and still found.
+    let b = matrix::Matrix::from(vec![
+        vec![r(1, 1)],
+        vec![r(1, 1)],
+        vec![r(3, 2) + r(1, 1_000_000_007)],
+    ]);
+    assert_eq!(rref.classify(&b), Consistency::Inconsistent);
+}
+#[test]
+fn flops_in_the_readme_table() {
+    use gauss_jordan_elimination::{gauss_jordan_with, GaussJordanOptions};
+    use matrix::count::{count_operations, Counted};
+    use matrix::trace::NoTrace;
+    //The diagonally dominant 30 x 30 matrix of the flop table in the README.
+    let n = 30;
+    let mut a = matrix::Matrix::new(n, n);
+    for row in 0..n {
+        for column in 0..n {
+            a.values[row][column] = Counted(match row == column {
+                true => 100.0,
+                false => ((row * 7 + column * 3) % 11) as f64 / 10.0,
+            });
+        }
+    }
+    let invert_flops = |return_inverse_in_a: bool| {
+        let options = GaussJordanOptions {
+            return_inverse_in_a,
+            ..GaussJordanOptions::default()
+        };
+        let (_, counts) = count_operations(|| {
+            let mut a = a.clone();
+            let mut b = match return_inverse_in_a {
+                true => matrix::Matrix::new(n, 0),
+                false => matrix::Matrix::identity(n),
+            };
+            gauss_jordan_with(&mut a, &mut b, options, &mut NoTrace).unwrap();
+        });
+        counts.flops()
+    };
+    assert_eq!(invert_flops(true), 53_130);
+    assert_eq!(invert_flops(false), 106_230);
+}
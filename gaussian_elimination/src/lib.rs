@@ -0,0 +1,170 @@
+use matrix::elimination::validate;
+pub use matrix::elimination::Error;
+use matrix::pivoting::{select_pivot, Pivoting, Pivots};
+use matrix::trace::{NoTrace, Step, Trace};
+use matrix::Scalar;
+
+///The choices gaussian_elimination may make.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GaussianEliminationOptions {
+    ///How each pivot is chosen. Partial pivoting, the choice of the book, searches the pivot column on and below the diagonal.
+    pub pivoting: Pivoting,
+}
+
+impl Default for GaussianEliminationOptions {
+    fn default() -> Self {
+        GaussianEliminationOptions {
+            pivoting: Pivoting::Partial,
+        }
+    }
+}
+
+///Linear equation solution by Gaussian elimination with back-substitution, section 2.2. The input matrix
+///is a[0..n-1][0..n-1]. b[0..n-1][0..m-1] is input containing the m right-hand side vectors.
+///On output, a is replaced by the upper triangular matrix of the elimination, and b is replaced by the
+///corresponding set of solution vectors.
+///
+///### Usage
+/// Gauss-Jordan eliminates above and below each pivot. Gaussian elimination eliminates only below it,
+/// then solves the triangular system that is left by back-substitution, for about a third of the work.
+/// ```
+///    use gaussian_elimination::gaussian_elimination;
+///    let mut a = matrix::Matrix::from(vec![vec![3.0, -2.0], vec![6.0, 4.0]]);
+///    let mut b = matrix::Matrix::from(vec![vec![4.0], vec![-8.0]]);
+///    gaussian_elimination(&mut a, &mut b).unwrap();
+///    assert_eq!(b, matrix::Matrix::from(vec![vec![0.0], vec![-2.0]]));
+///    assert_eq!(a, matrix::Matrix::from(vec![vec![6.0, 4.0], vec![0.0, -4.0]]));
+/// ```
+///### Errors
+/// `NotSquare`, `DimensionMismatch` and `NonFiniteInput` are returned before anything is written, leaving a and b untouched.
+/// `SingularMatrix` is returned part way through the elimination, leaving a and b as reduced by the steps before `step`.
+pub fn gaussian_elimination<T: Scalar>(
+    a: &mut matrix::Matrix<T>,
+    b: &mut matrix::Matrix<T>,
+) -> Result<(), Error> {
+    gaussian_elimination_with(a, b, GaussianEliminationOptions::default(), &mut NoTrace)?;
+    Ok(())
+}
+
+///Performs gaussian_elimination with the given pivoting, reporting each pivot selection, swap, elimination
+///and substitution to `trace`.
+///
+/// Returns where each pivot was found. The pivot of step k was found at (rows[k], columns[k]),
+/// and swapped to (k, k). Column swaps are undone on the solution before returning, and stay in a.
+pub fn gaussian_elimination_with<T: Scalar>(
+    a: &mut matrix::Matrix<T>,
+    b: &mut matrix::Matrix<T>,
+    options: GaussianEliminationOptions,
+    trace: &mut impl Trace<T>,
+) -> Result<Pivots, Error> {
+    validate(a, b)?;
+    trace.start(a, Some(b));
+    let n = a.rows();
+    let m = b.columns();
+    let mut pivots = Pivots::default();
+    //Reciprocals of the largest absolute value in each row, for scaled partial pivoting. They follow their rows when swapped.
+    let mut scales = vec![T::one(); n];
+    if options.pivoting == Pivoting::ScaledPartial {
+        for row in 0..n {
+            let max = a.row_abs_max(row).0;
+            if max != T::zero() {
+                scales[row] = T::one() / max;
+            }
+        }
+    }
+
+    //Forward elimination reduces A to upper triangular form, one column at a time.
+    '_forward_elimination: for step in 0..n {
+        let remaining: Vec<usize> = (step..n).collect();
+        let (pivot_row, pivot_column) =
+            match select_pivot(a, &remaining, &remaining, options.pivoting, &scales) {
+                Some(location) => location,
+                None => return Err(Error::SingularMatrix { step }),
+            };
+        pivots.rows.push(pivot_row);
+        pivots.columns.push(pivot_column);
+        trace.record(
+            Step::SelectPivot {
+                row: pivot_row,
+                column: pivot_column,
+            },
+            a,
+            Some(b),
+        );
+
+        //Move the pivot onto the diagonal.
+        if pivot_row != step {
+            a.swap_rows((pivot_row, step));
+            b.swap_rows((pivot_row, step));
+            scales.swap(pivot_row, step);
+            trace.record(
+                Step::SwapRows {
+                    rows: (pivot_row, step),
+                },
+                a,
+                Some(b),
+            );
+        }
+        if pivot_column != step {
+            a.swap_columns((pivot_column, step));
+            trace.record(
+                Step::SwapColumns {
+                    columns: (pivot_column, step),
+                },
+                a,
+                Some(b),
+            );
+        }
+
+        //Subtract a multiple of the pivot row from each row below it, to zero the pivot column below the diagonal.
+        let pivot_inverse = T::one() / a.values[step][step].clone();
+        '_traverse_rows_below: for row in step + 1..n {
+            let factor = a.values[row][step].clone() * pivot_inverse.clone();
+            a.values[row][step] = T::zero();
+            '_traverse_elements_in_row: for column in step + 1..n {
+                let product = factor.clone() * a.values[step][column].clone();
+                a.values[row][column] -= product;
+            }
+            for column in 0..m {
+                let product = factor.clone() * b.values[step][column].clone();
+                b.values[row][column] -= product;
+            }
+            trace.record(
+                Step::EliminateRow {
+                    target: row,
+                    source: step,
+                    factor,
+                },
+                a,
+                Some(b),
+            );
+        }
+    }
+
+    //Back-substitution solves the triangular system from the last row up, equation (2.2.4).
+    '_back_substitution: for row in (0..n).rev() {
+        for column in 0..m {
+            let mut sum = b.values[row][column].clone();
+            for k in row + 1..n {
+                sum -= a.values[row][k].clone() * b.values[k][column].clone();
+            }
+            b.values[row][column] = sum / a.values[row][row].clone();
+            trace.record(Step::Substitute { row, column }, a, Some(b));
+        }
+    }
+
+    //The solution is of the unknowns in the order of the swapped columns. Undo the column swaps in reverse.
+    '_unscramble_solution: for (step, pivot_column) in pivots.columns.iter().enumerate().rev() {
+        if step == *pivot_column {
+            continue;
+        }
+        let rows = (step, *pivot_column);
+        for column in 0..m {
+            let temp = b.values[rows.0][column].clone();
+            b.values[rows.0][column] = b.values[rows.1][column].clone();
+            b.values[rows.1][column] = temp;
+            trace.record(Step::SwapElements { rows, column }, a, Some(b));
+        }
+    }
+    Ok(pivots)
+}
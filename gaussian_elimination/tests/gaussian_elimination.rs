@@ -0,0 +1,171 @@
+#[test]
+fn return_solution_in_place_of_b() {
+    use gaussian_elimination::gaussian_elimination;
+    let mut matrix_a = matrix::Matrix::from(vec![
+        vec![2.0, 1.0, -1.0],
+        vec![-3.0, -1.0, 2.0],
+        vec![-2.0, 1.0, 2.0],
+    ]);
+    let mut matrix_b =
+        matrix::Matrix::from(vec![vec![8.0, 1.0], vec![-11.0, 0.0], vec![-3.0, 0.0]]);
+    gaussian_elimination(&mut matrix_a, &mut matrix_b).unwrap();
+
+    //The first column solves to [2, 3, -1]. The second is the first column of the inverse, [4, -2, 5].
+    let solution: [[f64; 2]; 3] = [[2.0, 4.0], [3.0, -2.0], [-1.0, 5.0]];
+    for row in 0..3 {
+        for column in 0..2 {
+            let error = matrix_b.values[row][column] - solution[row][column];
+            assert!(error.abs() < 1e-12);
+        }
+    }
+    //A is left upper triangular.
+    for row in 0..3 {
+        for column in 0..row {
+            assert_eq!(matrix_a.values[row][column], 0.0);
+        }
+    }
+}
+#[test]
+fn return_singular_matrix_at_step() {
+    use gaussian_elimination::{gaussian_elimination, Error};
+    let mut matrix_a = matrix::Matrix::from(vec![
+        vec![1.0, 2.0, 3.0],
+        vec![2.0, 4.0, 6.0],
+        vec![1.0, 0.0, 1.0],
+    ]);
+    let mut matrix_b = matrix::Matrix::from(vec![vec![1.0], vec![2.0], vec![3.0]]);
+    let result = gaussian_elimination(&mut matrix_a, &mut matrix_b);
+    assert_eq!(result, Err(Error::SingularMatrix { step: 2 }));
+}
+#[test]
+fn return_input_errors_without_modifying_inputs() {
+    use gaussian_elimination::{gaussian_elimination, Error};
+    let original_a = matrix::Matrix::from(vec![vec![3.0, -2.0], vec![6.0, 4.0]]);
+    let (mut a, mut b) = (original_a.clone(), matrix::Matrix::new(3, 1));
+    let result = gaussian_elimination(&mut a, &mut b);
+    assert_eq!(
+        result,
+        Err(Error::DimensionMismatch {
+            a_rows: 2,
+            b_rows: 3
+        })
+    );
+    assert_eq!(a, original_a);
+
+    let (mut a, mut b) = (matrix::Matrix::<f64>::new(2, 3), matrix::Matrix::new(2, 1));
+    let result = gaussian_elimination(&mut a, &mut b);
+    assert_eq!(
+        result,
+        Err(Error::NotSquare {
+            rows: 2,
+            columns: 3
+        })
+    );
+
+    let (mut a, mut b) = (
+        original_a.clone(),
+        matrix::Matrix::from(vec![vec![f64::NAN], vec![1.0]]),
+    );
+    let result = gaussian_elimination(&mut a, &mut b);
+    assert_eq!(result, Err(Error::NonFiniteInput));
+    assert_eq!(a, original_a);
+}
+#[test]
+fn every_pivoting_strategy_solves() {
+    use gaussian_elimination::{gaussian_elimination_with, GaussianEliminationOptions};
+    use matrix::pivoting::Pivoting;
+    use matrix::trace::NoTrace;
+    let original_a: matrix::Matrix<f64> = matrix::Matrix::from(vec![
+        vec![1.0, 5.0, 0.0],
+        vec![2.0, 3.0, 9.0],
+        vec![4.0, 6.0, 1.0],
+    ]);
+    //b = A * [1, 2, 3]
+    let original_b = matrix::Matrix::from(vec![vec![11.0], vec![35.0], vec![19.0]]);
+    for pivoting in [
+        Pivoting::None,
+        Pivoting::Partial,
+        Pivoting::ScaledPartial,
+        Pivoting::Complete,
+        Pivoting::Rook,
+    ] {
+        let (mut a, mut b) = (original_a.clone(), original_b.clone());
+        let options = GaussianEliminationOptions { pivoting };
+        let pivots = gaussian_elimination_with(&mut a, &mut b, options, &mut NoTrace).unwrap();
+        for (row, expected) in [1.0, 2.0, 3.0].iter().enumerate() {
+            assert!(
+                (b.values[row][0] - expected).abs() < 1e-12,
+                "{:?}",
+                pivoting
+            );
+        }
+        if pivoting == Pivoting::Complete {
+            assert_eq!((pivots.rows[0], pivots.columns[0]), (1, 2));
+        }
+    }
+}
+#[test]
+fn trace_ends_with_the_solution() {
+    use gaussian_elimination::{gaussian_elimination_with, GaussianEliminationOptions};
+    use matrix::trace::{Recorder, Step};
+    let mut a = matrix::Matrix::from(vec![vec![3.0, -2.0], vec![6.0, 4.0]]);
+    let mut b = matrix::Matrix::from(vec![vec![4.0], vec![-8.0]]);
+    let mut recorder = Recorder::new();
+    let options = GaussianEliminationOptions::default();
+    gaussian_elimination_with(&mut a, &mut b, options, &mut recorder).unwrap();
+    assert_eq!(
+        recorder.steps(),
+        vec![
+            Step::SelectPivot { row: 1, column: 0 },
+            Step::SwapRows { rows: (1, 0) },
+            Step::EliminateRow {
+                target: 1,
+                source: 0,
+                factor: 0.5
+            },
+            Step::SelectPivot { row: 1, column: 1 },
+            Step::Substitute { row: 1, column: 0 },
+            Step::Substitute { row: 0, column: 0 },
+        ]
+    );
+    for pair in recorder.frames().windows(2) {
+        assert_eq!(pair[0].after, pair[1].before);
+    }
+    assert_eq!(recorder.frames().last().unwrap().after.b, Some(b));
+}
+#[test]
+fn gaussian_elimination_costs_about_a_third_of_gauss_jordan() {
+    use gauss_jordan_elimination::{gauss_jordan_with, GaussJordanOptions};
+    use gaussian_elimination::{gaussian_elimination_with, GaussianEliminationOptions};
+    use matrix::count::{count_operations, Counted};
+    use matrix::pivoting::Pivoting;
+    use matrix::trace::NoTrace;
+    let n = 30;
+    let mut a = matrix::Matrix::new(n, n);
+    for row in 0..n {
+        for column in 0..n {
+            a.values[row][column] = Counted(((row * 7 + column * 3) % 11) as f64);
+        }
+        a.values[row][row] = Counted(100.0);
+    }
+    let b = matrix::Matrix::from(vec![vec![Counted(1.0)]; n]);
+
+    let (_, gaussian_counts) = count_operations(|| {
+        let (mut a, mut b) = (a.clone(), b.clone());
+        let options = GaussianEliminationOptions::default();
+        gaussian_elimination_with(&mut a, &mut b, options, &mut NoTrace).unwrap();
+    });
+    let (_, gauss_jordan_counts) = count_operations(|| {
+        let (mut a, mut b) = (a.clone(), b.clone());
+        let options = GaussJordanOptions {
+            return_inverse_in_a: false,
+            pivoting: Pivoting::Partial,
+            ..GaussJordanOptions::default()
+        };
+        gauss_jordan_with(&mut a, &mut b, options, &mut NoTrace).unwrap();
+    });
+
+    //N^3 / 3 against N^3 multiplications, less lower order terms.
+    let ratio = gauss_jordan_counts.multiplications as f64 / gaussian_counts.multiplications as f64;
+    assert!((2.5..3.5).contains(&ratio), "ratio was {ratio}");
+}
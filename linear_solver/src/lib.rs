@@ -0,0 +1,354 @@
+use gauss_jordan_elimination::{gauss_jordan_with, GaussJordanOptions};
+use lu_decomposition::{DecomposeOptions, LuDecomposition, SingularPivot};
+use matrix::trace::{Step, Trace};
+use matrix::{Matrix, Scalar};
+use std::fmt;
+use std::io::{BufReader, Read, Write};
+
+mod read;
+mod repl;
+mod write;
+
+use read::is_finite;
+pub use read::read_matrix;
+pub use repl::repl;
+pub use write::write_matrix;
+
+pub const USAGE: &str = "\
+Usage: linear_solver [OPTIONS] [A]
+
+Solves A * X = B. A is read from the file A, or from stdin when A is - or missing.
+Files may be CSV, or Matrix Market when they start with %%MatrixMarket.
+Without --rhs, the columns of A after the first n are B, as in the augmented matrix [A | B].
+
+Options:
+  -b, --rhs <FILE>        Read B from FILE.
+  -m, --method <METHOD>   lu or gauss-jordan. Default lu.
+  -o, --output <OUTPUT>   solution, inverse or determinant. Default solution.
+  -f, --format <FORMAT>   plain, csv or matrix-market. Default plain.
+  -i, --interactive       Start a shell for row operations on A and B. Enter help for its commands.
+  -h, --help              Print this message.
+
+Exit codes:
+  0  Success.
+  1  A file could not be read or written.
+  2  The command line could not be understood.
+  3  A or B could not be parsed.
+  4  A or B have the wrong shape.
+  5  A is singular.
+  6  A or B holds a NaN or an infinity.
+";
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    ///The file at `path` could not be read or written.
+    Io { path: String, message: String },
+    ///The command line could not be understood.
+    Usage(String),
+    ///The input could not be parsed at `line`, counted from 1.
+    Parse { line: usize, message: String },
+    ///A is not square, or B does not have a row for each row of A.
+    Shape(String),
+    ///A has no inverse.
+    SingularMatrix,
+    ///A or B holds a NaN or an infinity.
+    NonFiniteInput,
+}
+
+impl Error {
+    ///The code the process exits with. Each kind of error has its own, listed in USAGE.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Io { .. } => 1,
+            Error::Usage(_) => 2,
+            Error::Parse { .. } => 3,
+            Error::Shape(_) => 4,
+            Error::SingularMatrix => 5,
+            Error::NonFiniteInput => 6,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io { path, message } => write!(formatter, "{}: {}", path, message),
+            Error::Usage(message) => write!(formatter, "{}\n\n{}", message, USAGE),
+            Error::Parse { line, message } => write!(formatter, "line {}: {}", line, message),
+            Error::Shape(message) => write!(formatter, "{}", message),
+            Error::SingularMatrix => write!(formatter, "the matrix is singular"),
+            Error::NonFiniteInput => write!(formatter, "the input holds a NaN or an infinity"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Method {
+    LuDecomposition,
+    GaussJordan,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Output {
+    Solution,
+    Inverse,
+    Determinant,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Plain,
+    Csv,
+    MatrixMarket,
+}
+
+///The parsed command line.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Arguments {
+    ///The file holding A. None reads stdin.
+    pub a: Option<String>,
+    ///The file holding B. None takes B from the columns of A after the first n.
+    pub b: Option<String>,
+    pub method: Method,
+    pub output: Output,
+    pub format: Format,
+    pub interactive: bool,
+    pub help: bool,
+}
+
+impl Default for Arguments {
+    fn default() -> Self {
+        Arguments {
+            a: None,
+            b: None,
+            method: Method::LuDecomposition,
+            output: Output::Solution,
+            format: Format::Plain,
+            interactive: false,
+            help: false,
+        }
+    }
+}
+
+///Parses the command line, without the program name.
+pub fn parse_arguments(arguments: &[String]) -> Result<Arguments, Error> {
+    let mut parsed = Arguments::default();
+    let mut arguments = arguments.iter();
+    '_traverse_arguments: while let Some(argument) = arguments.next() {
+        let mut value = || {
+            arguments
+                .next()
+                .ok_or_else(|| Error::Usage(format!("{} needs a value", argument)))
+        };
+        match argument.as_str() {
+            "-h" | "--help" => parsed.help = true,
+            "-i" | "--interactive" => parsed.interactive = true,
+            "-b" | "--rhs" => parsed.b = Some(value()?.clone()),
+            "-m" | "--method" => {
+                parsed.method = match value()?.as_str() {
+                    "lu" => Method::LuDecomposition,
+                    "gauss-jordan" => Method::GaussJordan,
+                    other => return Err(Error::Usage(format!("unknown method {}", other))),
+                }
+            }
+            "-o" | "--output" => {
+                parsed.output = match value()?.as_str() {
+                    "solution" => Output::Solution,
+                    "inverse" => Output::Inverse,
+                    "determinant" => Output::Determinant,
+                    other => return Err(Error::Usage(format!("unknown output {}", other))),
+                }
+            }
+            "-f" | "--format" => {
+                parsed.format = match value()?.as_str() {
+                    "plain" => Format::Plain,
+                    "csv" => Format::Csv,
+                    "matrix-market" => Format::MatrixMarket,
+                    other => return Err(Error::Usage(format!("unknown format {}", other))),
+                }
+            }
+            "-" => parsed.a = None,
+            option if option.starts_with('-') => {
+                return Err(Error::Usage(format!("unknown option {}", option)))
+            }
+            file if parsed.a.is_none() => parsed.a = Some(file.to_string()),
+            file => return Err(Error::Usage(format!("unexpected argument {}", file))),
+        }
+    }
+    Ok(parsed)
+}
+
+///Runs the solver on a command line, without the program name, writing the result to `stdout`.
+///### Usage
+/// ```
+///    let arguments: Vec<String> = ["--format", "csv"].iter().map(|s| s.to_string()).collect();
+///    let mut stdin = "3,-2,4\n6,4,-8\n".as_bytes();
+///    let mut stdout = Vec::new();
+///    linear_solver::run(&arguments, &mut stdin, &mut stdout).unwrap();
+///    assert_eq!(String::from_utf8(stdout).unwrap(), "0\n-2\n");
+/// ```
+pub fn run(
+    arguments: &[String],
+    stdin: &mut impl Read,
+    stdout: &mut impl Write,
+) -> Result<(), Error> {
+    let arguments = parse_arguments(arguments)?;
+    if arguments.help {
+        return write(stdout, USAGE);
+    }
+    if arguments.interactive {
+        return repl(BufReader::new(stdin), stdout).map_err(|error| Error::Io {
+            path: String::from("stdin"),
+            message: error.to_string(),
+        });
+    }
+
+    let mut a = read_matrix(&read_source(arguments.a.as_deref(), stdin)?)?;
+    let n = a.rows();
+    let b = match &arguments.b {
+        Some(path) => Some(read_matrix(&read_file(path)?)?),
+        None if a.columns() > n => Some(split_augmented(&mut a)),
+        None => None,
+    };
+    if a.columns() != n {
+        return Err(Error::Shape(format!(
+            "A has {} rows and {} columns. It must be square",
+            n,
+            a.columns()
+        )));
+    }
+    if let Some(b) = &b {
+        if b.rows() != n {
+            return Err(Error::Shape(format!(
+                "A has {} rows, but B has {} rows",
+                n,
+                b.rows()
+            )));
+        }
+    }
+    if arguments.output == Output::Solution && b.is_none() {
+        return Err(Error::Shape(String::from(
+            "there is no right hand side. Pass --rhs, or append B to the columns of A",
+        )));
+    }
+
+    if !is_finite(&a) || b.as_ref().is_some_and(|b| !is_finite(b)) {
+        return Err(Error::NonFiniteInput);
+    }
+
+    let result = solve(a, b, arguments.method, arguments.output)?;
+    write(stdout, &write_matrix(&result, arguments.format))
+}
+
+///Returns the solution, the inverse or the determinant of A, as a matrix.
+fn solve(
+    mut a: Matrix<f64>,
+    b: Option<Matrix<f64>>,
+    method: Method,
+    output: Output,
+) -> Result<Matrix<f64>, Error> {
+    let n = a.rows();
+    let mut b = b.unwrap_or_else(|| Matrix::new(n, 0));
+    match method {
+        Method::LuDecomposition => {
+            let options = DecomposeOptions {
+                singular_pivot: SingularPivot::Fail,
+                ..DecomposeOptions::default()
+            };
+            let lu =
+                LuDecomposition::with_options(a, options).map_err(|_| Error::SingularMatrix)?;
+            //Only an exactly zero pivot fails the decomposition. Rounding leaves most singular matrices with a tiny one,
+            //so a reciprocal condition number below epsilon is singular too, as it is to gauss_jordan.
+            if lu.reciprocal_condition() < <f64 as Scalar>::epsilon() {
+                return Err(Error::SingularMatrix);
+            }
+            Ok(match output {
+                Output::Solution => lu.solve(&b),
+                Output::Inverse => lu.inverse(),
+                Output::Determinant => Matrix::from(vec![vec![lu.determinant()]]),
+            })
+        }
+        Method::GaussJordan => {
+            let options = GaussJordanOptions::default();
+            let mut pivots = PivotTrace { determinant: 1.0 };
+            gauss_jordan_with(&mut a, &mut b, options, &mut pivots).map_err(
+                |error| match error {
+                    gauss_jordan_elimination::Error::SingularMatrix { .. } => Error::SingularMatrix,
+                    gauss_jordan_elimination::Error::NonFiniteInput => Error::NonFiniteInput,
+                    gauss_jordan_elimination::Error::DimensionMismatch { a_rows, b_rows } => {
+                        Error::Shape(format!("A has {} rows, but B has {} rows", a_rows, b_rows))
+                    }
+                    gauss_jordan_elimination::Error::NotSquare { rows, columns } => {
+                        Error::Shape(format!(
+                            "A has {} rows and {} columns. It must be square",
+                            rows, columns
+                        ))
+                    }
+                },
+            )?;
+            Ok(match output {
+                Output::Solution => b,
+                Output::Inverse => a,
+                Output::Determinant => Matrix::from(vec![vec![pivots.determinant]]),
+            })
+        }
+    }
+}
+
+///Removes the columns after the first n from the n x (n + m) augmented matrix [A | B], and returns them as B.
+fn split_augmented(a: &mut Matrix<f64>) -> Matrix<f64> {
+    let n = a.rows();
+    let b = a.values.iter_mut().map(|row| row.split_off(n)).collect();
+    *a = Matrix::from(std::mem::take(&mut a.values));
+    Matrix::from(b)
+}
+
+///Watches the pivots chosen by Gauss-Jordan.
+///
+/// Gauss-Jordan reduces A to the identity by swapping rows, dividing rows by pivots and adding multiples of rows.
+/// Only the first two change the determinant, so det(A) is the product of the pivots, negated for each swap.
+struct PivotTrace {
+    determinant: f64,
+}
+
+impl Trace<f64> for PivotTrace {
+    fn record(&mut self, step: Step<f64>, a: &Matrix<f64>, _b: Option<&Matrix<f64>>) {
+        match step {
+            Step::SelectPivot { row, column } => {
+                self.determinant *= a.values[row][column];
+            }
+            Step::SwapRows { .. } => self.determinant = -self.determinant,
+            _ => {}
+        }
+    }
+}
+
+fn read_source(path: Option<&str>, stdin: &mut impl Read) -> Result<String, Error> {
+    match path {
+        Some(path) => read_file(path),
+        None => {
+            let mut text = String::new();
+            stdin.read_to_string(&mut text).map_err(|error| Error::Io {
+                path: String::from("stdin"),
+                message: error.to_string(),
+            })?;
+            Ok(text)
+        }
+    }
+}
+
+fn read_file(path: &str) -> Result<String, Error> {
+    std::fs::read_to_string(path).map_err(|error| Error::Io {
+        path: path.to_string(),
+        message: error.to_string(),
+    })
+}
+
+fn write(stdout: &mut impl Write, text: &str) -> Result<(), Error> {
+    stdout
+        .write_all(text.as_bytes())
+        .map_err(|error| Error::Io {
+            path: String::from("stdout"),
+            message: error.to_string(),
+        })
+}
@@ -0,0 +1,8 @@
+fn main() {
+    let arguments: Vec<String> = std::env::args().skip(1).collect();
+    let result = linear_solver::run(&arguments, &mut std::io::stdin(), &mut std::io::stdout());
+    if let Err(error) = result {
+        eprintln!("linear_solver: {}", error);
+        std::process::exit(error.exit_code());
+    }
+}
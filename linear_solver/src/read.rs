@@ -0,0 +1,181 @@
+use crate::Error;
+use matrix::Matrix;
+
+///Reads a dense matrix from CSV or Matrix Market text.
+///
+/// Text whose first line starts with %%MatrixMarket is read as Matrix Market. Anything else is read as CSV.
+/// NaN, inf and -inf are read as they are written. See Error::NonFiniteInput.
+pub fn read_matrix(text: &str) -> Result<Matrix<f64>, Error> {
+    if text.trim_start().starts_with("%%MatrixMarket") {
+        matrix_market(text)
+    } else {
+        csv(text)
+    }
+}
+
+///Reads one row per line, with values separated by commas.
+///Blank lines and lines starting with # are skipped.
+fn csv(text: &str) -> Result<Matrix<f64>, Error> {
+    let mut rows: Vec<Vec<f64>> = Vec::new();
+    '_traverse_lines: for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut row = Vec::new();
+        for field in line.split(',') {
+            row.push(number(field, index + 1)?);
+        }
+        if let Some(first) = rows.first() {
+            if first.len() != row.len() {
+                return Err(Error::Parse {
+                    line: index + 1,
+                    message: format!("expected {} values, found {}", first.len(), row.len()),
+                });
+            }
+        }
+        rows.push(row);
+    }
+    if rows.is_empty() {
+        return Err(Error::Parse {
+            line: 1,
+            message: String::from("no values"),
+        });
+    }
+    Ok(Matrix::from(rows))
+}
+
+///Reads the real and integer matrices of the Matrix Market exchange format, in array or coordinate layout,
+///general or symmetric.
+fn matrix_market(text: &str) -> Result<Matrix<f64>, Error> {
+    let last_line = text.lines().count();
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()));
+    let (_, header) = lines.next().unwrap_or((1, ""));
+    let header: Vec<String> = header.split_whitespace().map(str::to_lowercase).collect();
+    let header: Vec<&str> = header.iter().map(String::as_str).collect();
+    let (layout, symmetric) = match header.as_slice() {
+        [_, "matrix", layout, "real" | "integer", symmetry] => {
+            let symmetric = match *symmetry {
+                "general" => false,
+                "symmetric" => true,
+                other => return Err(unsupported(other)),
+            };
+            (*layout, symmetric)
+        }
+        [_, "matrix", _, field, ..] => return Err(unsupported(field)),
+        _ => {
+            return Err(Error::Parse {
+                line: 1,
+                message: String::from("expected %%MatrixMarket matrix <layout> <field> <symmetry>"),
+            })
+        }
+    };
+
+    //Comments and blank lines may appear anywhere after the header.
+    let mut lines = lines.filter(|(_, line)| !line.is_empty() && !line.starts_with('%'));
+    let (size_line, size) = lines.next().ok_or(Error::Parse {
+        line: 1,
+        message: String::from("missing size line"),
+    })?;
+    let size = size
+        .split_whitespace()
+        .map(|field| index(field, size_line))
+        .collect::<Result<Vec<usize>, Error>>()?;
+    if size.len() >= 2 && (size[0] == 0 || size[1] == 0 || symmetric && size[0] != size[1]) {
+        return Err(Error::Parse {
+            line: size_line,
+            message: String::from("the matrix must be non-empty, and square if symmetric"),
+        });
+    }
+
+    match (layout, size.as_slice()) {
+        ("array", [rows, columns]) => {
+            //Values are listed column by column. Symmetric matrices list only the lower triangle.
+            let mut matrix = Matrix::new(*rows, *columns);
+            '_traverse_columns: for column in 0..*columns {
+                let first_row = if symmetric { column } else { 0 };
+                for row in first_row..*rows {
+                    let (line, field) = lines.next().ok_or(Error::Parse {
+                        line: last_line,
+                        message: format!("missing the value at ({}, {})", row + 1, column + 1),
+                    })?;
+                    let value = number(field, line)?;
+                    matrix.values[row][column] = value;
+                    if symmetric {
+                        matrix.values[column][row] = value;
+                    }
+                }
+            }
+            Ok(matrix)
+        }
+        ("coordinate", [rows, columns, entries]) => {
+            let mut matrix = Matrix::new(*rows, *columns);
+            for _ in 0..*entries {
+                let (line, entry) = lines.next().ok_or(Error::Parse {
+                    line: last_line,
+                    message: format!("expected {} entries", entries),
+                })?;
+                let fields: Vec<&str> = entry.split_whitespace().collect();
+                let [row, column, value] = fields.as_slice() else {
+                    return Err(Error::Parse {
+                        line,
+                        message: String::from("expected <row> <column> <value>"),
+                    });
+                };
+                //Matrix Market indices start at 1.
+                let (row, column) = (index(row, line)?, index(column, line)?);
+                if row == 0 || row > *rows || column == 0 || column > *columns {
+                    return Err(Error::Parse {
+                        line,
+                        message: format!("({}, {}) is outside the matrix", row, column),
+                    });
+                }
+                let value = number(value, line)?;
+                matrix.values[row - 1][column - 1] = value;
+                if symmetric {
+                    matrix.values[column - 1][row - 1] = value;
+                }
+            }
+            Ok(matrix)
+        }
+        _ => Err(Error::Parse {
+            line: size_line,
+            message: format!("unexpected size line for the {} layout", layout),
+        }),
+    }
+}
+
+///Parses a value. NaN and the infinities parse, so that they are reported as NonFiniteInput rather than as text
+/// that could not be read.
+fn number(field: &str, line: usize) -> Result<f64, Error> {
+    field.trim().parse::<f64>().map_err(|_| Error::Parse {
+        line,
+        message: format!("{:?} is not a number", field.trim()),
+    })
+}
+
+///Returns false if the matrix holds a NaN or an infinity.
+pub(crate) fn is_finite(matrix: &Matrix<f64>) -> bool {
+    matrix
+        .values
+        .iter()
+        .flatten()
+        .all(|value| value.is_finite())
+}
+
+fn index(field: &str, line: usize) -> Result<usize, Error> {
+    field.parse().map_err(|_| Error::Parse {
+        line,
+        message: format!("{:?} is not a size or an index", field),
+    })
+}
+
+fn unsupported(qualifier: &str) -> Error {
+    Error::Parse {
+        line: 1,
+        message: format!("{} matrices are not supported", qualifier),
+    }
+}
@@ -0,0 +1,248 @@
+use crate::read::is_finite;
+use crate::{read_matrix, write_matrix, Error, Format};
+use gauss_jordan_elimination::gauss_jordan;
+use lu_decomposition::{crout, decompose};
+use matrix::Matrix;
+use std::io::{self, BufRead, Write};
+
+pub const HELP: &str = "\
+Commands act on the system A | B. Rows and columns are numbered from 1.
+  a <rows>                  Define A, e.g. a 3,-2; 6,4
+  b <rows>                  Define B, e.g. b 4; -8
+  load a|b <file>           Read A or B from a CSV or Matrix Market file.
+  swap <row> <row>          Swap two rows of A and B.
+  scale <row> <factor>      Multiply a row of A and B by factor.
+  eliminate <row> <source> <factor>
+                            Subtract factor times the source row from row, in A and B.
+  swapcolumns <col> <col>   Swap two columns of A.
+  newrow                    Append a row of zeros to A and B.
+  decompose                 Replace A with its packed LU decomposition.
+  crout                     Solve for B with the last decomposition.
+  gaussjordan               Replace A with its inverse and B with the solution.
+  undo                      Take back the last command that changed A or B.
+  show                      Print A and B.
+  help                      Print this message.
+  quit                      Leave.
+";
+
+///The matrices being worked on, and the row permutation of the last decomposition.
+/// The permutation is cleared by any command that changes A other than decompose, so crout never uses stale factors.
+#[derive(Clone, Debug, Default)]
+struct State {
+    a: Option<Matrix<f64>>,
+    b: Option<Matrix<f64>>,
+    permutation: Option<Vec<usize>>,
+}
+
+///An interactive shell for applying row operations and solvers to A and B, one command per line.
+///
+///### Usage
+/// Reads commands from `input` until it ends or quit is entered. The state is printed after every command.
+/// Errors are printed, and leave the state untouched.
+/// ```
+///    let script = "a 2,1; 4,3\nb 1; 1\neliminate 2 1 2\nundo\n";
+///    let mut output = Vec::new();
+///    linear_solver::repl(script.as_bytes(), &mut output).unwrap();
+///    let output = String::from_utf8(output).unwrap();
+///    assert!(output.contains("A =\n2  1\n0  1\n"));
+///    assert!(output.ends_with("A =\n2  1\n4  3\nB =\n1\n1\n> "));
+/// ```
+pub fn repl(input: impl BufRead, output: &mut impl Write) -> io::Result<()> {
+    let mut state = State::default();
+    let mut history: Vec<State> = Vec::new();
+    write!(output, "> ")?;
+    '_read_commands: for line in input.lines() {
+        let line = line?;
+        let command = line.trim();
+        match command {
+            "" => {}
+            "quit" | "exit" => break,
+            "help" => write!(output, "{}", HELP)?,
+            "show" => write!(output, "{}", show(&state))?,
+            "undo" => match history.pop() {
+                Some(previous) => {
+                    state = previous;
+                    write!(output, "{}", show(&state))?;
+                }
+                None => writeln!(output, "error: nothing to undo")?,
+            },
+            command => match execute(&state, command) {
+                Ok(next) => {
+                    history.push(std::mem::replace(&mut state, next));
+                    write!(output, "{}", show(&state))?;
+                }
+                Err(message) => writeln!(output, "error: {}", message)?,
+            },
+        }
+        write!(output, "> ")?;
+    }
+    Ok(())
+}
+
+///Returns the state after `command`, leaving `state` as it was.
+fn execute(state: &State, command: &str) -> Result<State, String> {
+    let (name, rest) = command.split_once(' ').unwrap_or((command, ""));
+    let arguments: Vec<&str> = rest.split_whitespace().collect();
+    let mut next = state.clone();
+    let changes_a = matches!(
+        name,
+        "a" | "load" | "swap" | "scale" | "eliminate" | "swapcolumns" | "newrow" | "gaussjordan"
+    );
+    match (name, arguments.as_slice()) {
+        ("a", _) => {
+            next.a = Some(parse_rows(rest)?);
+        }
+        ("b", _) => next.b = Some(parse_rows(rest)?),
+        ("load", [target, path]) => {
+            let text = std::fs::read_to_string(path).map_err(|error| error.to_string())?;
+            let matrix = finite(read_matrix(&text))?;
+            match *target {
+                "a" => next.a = Some(matrix),
+                "b" => next.b = Some(matrix),
+                other => return Err(format!("unknown matrix {}", other)),
+            }
+        }
+        ("swap", [first, second]) => {
+            let rows = (row(&next, first)?, row(&next, second)?);
+            for matrix in matrices(&mut next) {
+                matrix.swap_rows(rows);
+            }
+        }
+        ("scale", [target, factor]) => {
+            let target = row(&next, target)?;
+            let factor = number(factor)?;
+            for matrix in matrices(&mut next) {
+                matrix.scale_row(target, factor);
+            }
+        }
+        ("eliminate", [target, source, factor]) => {
+            let (target, source) = (row(&next, target)?, row(&next, source)?);
+            let factor = number(factor)?;
+            for matrix in matrices(&mut next) {
+                '_traverse_columns: for column in 0..matrix.columns() {
+                    let product = factor * matrix.values[source][column];
+                    matrix.values[target][column] -= product;
+                }
+            }
+        }
+        ("swapcolumns", [first, second]) => {
+            let a = next.a.as_mut().ok_or("define A first")?;
+            let columns = (index(first, a.columns())?, index(second, a.columns())?);
+            a.swap_columns(columns);
+        }
+        ("newrow", []) => {
+            for matrix in matrices(&mut next) {
+                matrix.new_row();
+            }
+        }
+        ("decompose", []) => {
+            let a = square(&mut next.a)?;
+            let (permutation, _) = decompose(a).map_err(|error| format!("{:?}", error))?;
+            next.permutation = Some(permutation);
+        }
+        ("crout", []) => {
+            let permutation = next.permutation.clone().ok_or("decompose A first")?;
+            let (a, b) = system(&mut next)?;
+            crout(a, &permutation, b);
+        }
+        ("gaussjordan", []) => {
+            let rows = square(&mut next.a)?.rows();
+            let mut b = next.b.take().unwrap_or_else(|| Matrix::new(rows, 0));
+            let a = next.a.as_mut().ok_or("define A first")?;
+            gauss_jordan(a, &mut b, true).map_err(|error| format!("{:?}", error))?;
+            next.b = Some(b).filter(|b| b.columns() > 0);
+        }
+        _ => {
+            return Err(format!(
+                "unknown command {:?}. Enter help for a list",
+                command
+            ))
+        }
+    }
+    if changes_a {
+        next.permutation = None;
+    }
+    Ok(next)
+}
+
+///Returns A and B, if they are defined.
+fn matrices(state: &mut State) -> impl Iterator<Item = &mut Matrix<f64>> {
+    state.a.iter_mut().chain(state.b.iter_mut())
+}
+
+///Returns A, if it is defined and square.
+fn square(a: &mut Option<Matrix<f64>>) -> Result<&mut Matrix<f64>, String> {
+    let a = a.as_mut().ok_or("define A first")?;
+    if a.rows() != a.columns() {
+        return Err(format!(
+            "A is {} x {}. It must be square",
+            a.rows(),
+            a.columns()
+        ));
+    }
+    Ok(a)
+}
+
+///Returns A and B, if both are defined and B has a row for each row of A.
+fn system(state: &mut State) -> Result<(&Matrix<f64>, &mut Matrix<f64>), String> {
+    let a = state.a.as_ref().ok_or("define A first")?;
+    let b = state.b.as_mut().ok_or("define B first")?;
+    if a.rows() != b.rows() {
+        return Err(format!("A has {} rows, but B has {}", a.rows(), b.rows()));
+    }
+    Ok((a, b))
+}
+
+///Converts a row number counted from 1 into an index, checking it against every defined matrix.
+fn row(state: &State, field: &str) -> Result<usize, String> {
+    let rows = [&state.a, &state.b]
+        .iter()
+        .filter_map(|matrix| matrix.as_ref().map(Matrix::rows))
+        .min()
+        .ok_or("define A or B first")?;
+    index(field, rows)
+}
+
+fn index(field: &str, count: usize) -> Result<usize, String> {
+    match field.parse::<usize>() {
+        Ok(number) if (1..=count).contains(&number) => Ok(number - 1),
+        _ => Err(format!("{} is not between 1 and {}", field, count)),
+    }
+}
+
+fn number(field: &str) -> Result<f64, String> {
+    match field.parse::<f64>() {
+        Ok(value) if value.is_finite() => Ok(value),
+        _ => Err(format!("{} is not a finite number", field)),
+    }
+}
+
+///Parses rows separated by semicolons, of values separated by commas.
+fn parse_rows(text: &str) -> Result<Matrix<f64>, String> {
+    finite(read_matrix(&text.replace(';', "\n")))
+}
+
+///Keeps NaN and the infinities out of the session, as run() keeps them out of the solvers.
+fn finite(matrix: Result<Matrix<f64>, Error>) -> Result<Matrix<f64>, String> {
+    match matrix {
+        Ok(matrix) if !is_finite(&matrix) => Err(Error::NonFiniteInput.to_string()),
+        matrix => matrix.map_err(|error| error.to_string()),
+    }
+}
+
+fn show(state: &State) -> String {
+    let mut text = String::new();
+    for (name, matrix) in [("A", &state.a), ("B", &state.b)] {
+        if let Some(matrix) = matrix {
+            text += &format!("{} =\n{}", name, write_matrix(matrix, Format::Plain));
+        }
+    }
+    if let Some(permutation) = &state.permutation {
+        let rows: Vec<String> = permutation
+            .iter()
+            .map(|row| (row + 1).to_string())
+            .collect();
+        text += &format!("Pivot rows: {}\n", rows.join(" "));
+    }
+    text
+}
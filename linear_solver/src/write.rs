@@ -0,0 +1,63 @@
+use crate::Format;
+use matrix::Matrix;
+
+///Writes a matrix as text in the given format. Every line ends with a newline.
+pub fn write_matrix(matrix: &Matrix<f64>, format: Format) -> String {
+    match format {
+        Format::Plain => plain(matrix),
+        Format::Csv => csv(matrix),
+        Format::MatrixMarket => matrix_market(matrix),
+    }
+}
+
+///Right aligns the values of each column, separated by two spaces.
+fn plain(matrix: &Matrix<f64>) -> String {
+    let columns = matrix.columns();
+    let mut widths = vec![0; columns];
+    for row in matrix.values() {
+        for (column, value) in row.iter().enumerate() {
+            widths[column] = widths[column].max(number(*value).len());
+        }
+    }
+    let mut text = String::new();
+    for row in matrix.values() {
+        let fields: Vec<String> = row
+            .iter()
+            .enumerate()
+            .map(|(column, value)| format!("{:>width$}", number(*value), width = widths[column]))
+            .collect();
+        text += &fields.join("  ");
+        text += "\n";
+    }
+    text
+}
+
+fn csv(matrix: &Matrix<f64>) -> String {
+    let mut text = String::new();
+    for row in matrix.values() {
+        let fields: Vec<String> = row.iter().map(|value| number(*value)).collect();
+        text += &fields.join(",");
+        text += "\n";
+    }
+    text
+}
+
+///Writes the dense array layout, which lists values column by column.
+fn matrix_market(matrix: &Matrix<f64>) -> String {
+    let mut text = String::from("%%MatrixMarket matrix array real general\n");
+    text += &format!("{} {}\n", matrix.rows(), matrix.columns());
+    for column in 0..matrix.columns() {
+        for row in 0..matrix.rows() {
+            text += &format!("{}\n", number(matrix.values[row][column]));
+        }
+    }
+    text
+}
+
+///Formats a value so that it reads back exactly. Negative zero is written as 0.
+fn number(value: f64) -> String {
+    if value == 0.0 {
+        return String::from("0");
+    }
+    value.to_string()
+}
@@ -0,0 +1,145 @@
+use linear_solver::{read_matrix, run, Error};
+
+fn solve(arguments: &[&str], stdin: &str) -> Result<String, Error> {
+    let arguments: Vec<String> = arguments
+        .iter()
+        .map(|argument| argument.to_string())
+        .collect();
+    let mut stdout = Vec::new();
+    run(&arguments, &mut stdin.as_bytes(), &mut stdout)?;
+    Ok(String::from_utf8(stdout).unwrap())
+}
+
+///Writes `text` to a file in the temporary directory, and returns its path.
+fn temporary_file(name: &str, text: &str) -> String {
+    let path = std::env::temp_dir().join(format!("linear_solver_{}_{}", std::process::id(), name));
+    std::fs::write(&path, text).unwrap();
+    path.to_string_lossy().into_owned()
+}
+
+#[test]
+fn solve_augmented_csv_from_stdin() {
+    let augmented = "3, -2, 4\n6, 4, -8\n";
+    assert_eq!(solve(&[], augmented).unwrap(), " 0\n-2\n");
+    assert_eq!(
+        solve(&["--method", "gauss-jordan", "-f", "csv"], augmented).unwrap(),
+        "0\n-2\n"
+    );
+}
+
+#[test]
+fn solve_with_rhs_file() {
+    let a = temporary_file("a.csv", "# A\n3,-2\n6,4\n");
+    let b = temporary_file(
+        "b.mtx",
+        "%%MatrixMarket matrix array real general\n2 1\n4\n-8\n",
+    );
+    assert_eq!(
+        solve(&[&a, "--rhs", &b, "-f", "csv"], "").unwrap(),
+        "0\n-2\n"
+    );
+}
+
+#[test]
+fn inverse_and_determinant_by_either_method() {
+    let a = "3,-2\n6,4\n";
+    for method in ["lu", "gauss-jordan"] {
+        let inverse = solve(&["-m", method, "-o", "inverse", "-f", "matrix-market"], a).unwrap();
+        let inverse = read_matrix(&inverse).unwrap();
+        let expected = read_matrix("4,2\n-6,3\n").unwrap();
+        for row in 0..2 {
+            for column in 0..2 {
+                let error = inverse.values[row][column] - expected.values[row][column] / 24.0;
+                assert!(error.abs() < 1e-12, "{} inverse", method);
+            }
+        }
+        let determinant = solve(&["-m", method, "-o", "determinant", "-f", "csv"], a).unwrap();
+        let determinant: f64 = determinant.trim().parse().unwrap();
+        assert!((determinant - 24.0).abs() < 1e-12, "{} determinant", method);
+    }
+}
+
+#[test]
+fn read_matrix_market_coordinate_symmetric() {
+    let text = "%%MatrixMarket matrix coordinate real symmetric\n% comment\n3 3 4\n1 1 2\n2 1 -1\n3 2 -1\n3 3 2\n";
+    let matrix = read_matrix(text).unwrap();
+    assert_eq!(
+        matrix.values,
+        vec![
+            vec![2.0, -1.0, 0.0],
+            vec![-1.0, 0.0, -1.0],
+            vec![0.0, -1.0, 2.0]
+        ]
+    );
+}
+
+#[test]
+fn errors_have_distinct_exit_codes() {
+    let singular = solve(&["-o", "inverse"], "1,2\n2,4\n").unwrap_err();
+    assert_eq!(singular, Error::SingularMatrix);
+    let singular = solve(&["-m", "gauss-jordan"], "1,2,1\n2,4,1\n").unwrap_err();
+    assert_eq!(singular, Error::SingularMatrix);
+
+    let parse = solve(&[], "1,2\n2,x\n").unwrap_err();
+    assert_eq!(
+        parse,
+        Error::Parse {
+            line: 2,
+            message: String::from("\"x\" is not a number")
+        }
+    );
+    let shape = solve(&["-o", "inverse"], "1,2\n3,4\n5,6\n").unwrap_err();
+    let usage = solve(&["--method", "cholesky"], "").unwrap_err();
+    let io = solve(&["/nonexistent/a.csv"], "").unwrap_err();
+
+    let a = temporary_file("non_finite.csv", "1,2\n3,NaN\n");
+    let non_finite = solve(&[a.as_str(), "-o", "determinant"], "").unwrap_err();
+    for method in ["lu", "gauss-jordan"] {
+        let rhs = temporary_file("infinite.csv", "1\ninf\n");
+        let error = solve(&["-m", method, "--rhs", rhs.as_str()], "1,2\n3,4\n").unwrap_err();
+        assert_eq!(error, Error::NonFiniteInput, "{}", method);
+    }
+
+    let codes: Vec<i32> = [&io, &usage, &parse, &shape, &singular, &non_finite]
+        .iter()
+        .map(|error| error.exit_code())
+        .collect();
+    assert_eq!(codes, vec![1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn methods_agree_on_a_singular_matrix() {
+    //Rounding leaves LU a pivot of about 1e-16 rather than zero.
+    let a = "1,2,3,1\n4,5,6,1\n7,8,9,1\n";
+    for method in ["lu", "gauss-jordan"] {
+        for output in ["solution", "inverse", "determinant"] {
+            let error = solve(&["-m", method, "-o", output], a).unwrap_err();
+            assert_eq!(error, Error::SingularMatrix, "{} {}", method, output);
+        }
+    }
+}
+
+#[test]
+fn binary_exits_with_the_error_code() {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+    let mut child = Command::new(env!("CARGO_BIN_EXE_linear_solver"))
+        .args(["--output", "determinant"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"1,2\n2,4\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert_eq!(output.status.code(), Some(5));
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "linear_solver: the matrix is singular\n"
+    );
+}
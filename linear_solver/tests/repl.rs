@@ -0,0 +1,110 @@
+use linear_solver::repl;
+
+fn session(script: &str) -> String {
+    let mut output = Vec::new();
+    repl(script.as_bytes(), &mut output).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn repl_row_operations_by_hand() {
+    let output = session(
+        "a 3,-2; 6,4\n\
+         b 4; -8\n\
+         swap 1 2\n\
+         scale 1 0.5\n\
+         eliminate 2 1 1\n\
+         scale 2 -0.25\n\
+         eliminate 1 2 2\n",
+    );
+    let last_state = output.rsplit("> ").nth(1).unwrap();
+    assert_eq!(last_state, "A =\n3  0\n0  1\nB =\n 0\n-2\n");
+}
+
+#[test]
+fn repl_decompose_and_crout() {
+    let output = session("a 3,-2; 6,4\nb 4; -8\ndecompose\ncrout\n");
+    assert!(output.contains("Pivot rows: 1 2\n"));
+    assert!(output.ends_with("B =\n 0\n-2\nPivot rows: 1 2\n> "));
+}
+
+#[test]
+fn repl_row_operations_discard_the_decomposition() {
+    for operation in [
+        "swap 1 2",
+        "scale 1 2",
+        "eliminate 2 1 1",
+        "swapcolumns 1 2",
+        "newrow",
+    ] {
+        let output = session(&format!(
+            "a 3,-2; 6,4\nb 4; -8\ndecompose\n{}\ncrout\n",
+            operation
+        ));
+        assert!(
+            output.ends_with("error: decompose A first\n> "),
+            "{}",
+            operation
+        );
+    }
+}
+
+#[test]
+fn repl_gauss_jordan_inverse() {
+    let output = session("a 3,-2; 6,4\ngaussjordan\n");
+    let inverse = linear_solver::write_matrix(
+        &matrix::Matrix::from(vec![
+            vec![4.0 / 24.0, 2.0 / 24.0],
+            vec![-6.0 / 24.0, 3.0 / 24.0],
+        ]),
+        linear_solver::Format::Plain,
+    );
+    assert!(output.ends_with(&format!("A =\n{}> ", inverse)));
+}
+
+#[test]
+fn repl_undo_walks_back_through_history() {
+    let output = session("a 1,2; 3,4\nswap 1 2\nswapcolumns 1 2\nundo\nundo\nundo\nundo\n");
+    let states: Vec<&str> = output.split("> ").collect();
+    assert_eq!(states[4], "A =\n3  4\n1  2\n");
+    assert_eq!(states[5], "A =\n1  2\n3  4\n");
+    assert_eq!(states[6], "");
+    assert_eq!(states[7], "error: nothing to undo\n");
+}
+
+#[test]
+fn repl_errors_leave_the_state_untouched() {
+    let output = session(
+        "a 1,2; 3,4\nswap 1 3\nscale 1 x\na 1,NaN; 3,4\ncrout\nrotate\nnewrow\nshow\nquit\nshow\n",
+    );
+    assert!(output.contains("error: 3 is not between 1 and 2\n"));
+    assert!(output.contains("error: x is not a finite number\n"));
+    assert!(output.contains("error: the input holds a NaN or an infinity\n"));
+    assert!(output.contains("error: decompose A first\n"));
+    assert!(output.contains("error: unknown command \"rotate\". Enter help for a list\n"));
+    assert!(output.ends_with("A =\n1  2\n3  4\n0  0\n> "));
+}
+
+#[test]
+fn binary_starts_the_repl_with_interactive() {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+    let mut child = Command::new(env!("CARGO_BIN_EXE_linear_solver"))
+        .arg("--interactive")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"a 2\nscale 1 3\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "> A =\n2\n> A =\n6\n> "
+    );
+}
@@ -0,0 +1,189 @@
+use crate::{eliminate, DecomposeOptions, Error, Substitute};
+use matrix::pivoting::{Pivoting, Pivots};
+use matrix::trace::NoTrace;
+use matrix::{kernels, Matrix, Scalar};
+
+///The number of columns of the trailing matrix updated at a time, so that the rows of U being
+/// subtracted stay in cache while every row below the panel passes over them.
+const TILE: usize = 256;
+
+///Performs LU decomposition in place on a matrix A, a block of columns at a time.
+///
+///### Usage
+/// Produces packed L and U that agree with decompose up to rounding, since the trailing matrix is updated in a
+/// different order, along with its row permutation and parity. The result is passed to crout in the same way.
+/// ```
+///    use lu_decomposition::{crout, decompose, decompose_blocked};
+///    let a = matrix::Matrix::from(vec![vec![3.0, -2.0], vec![6.0, 4.0]]);
+///    let mut blocked = a.clone();
+///    let mut textbook = a;
+///    let (permutation, parity) = decompose_blocked(&mut blocked, 64).unwrap();
+///    assert_eq!((permutation.clone(), parity), decompose(&mut textbook).unwrap());
+///    assert_eq!(blocked, textbook);
+///    let mut b = matrix::Matrix::from(vec![vec![4.0], vec![-8.0]]);
+///    crout(&blocked, &permutation, &mut b);
+///    assert_eq!(b, matrix::Matrix::from(vec![vec![0.0], vec![-2.0]]));
+/// ```
+///### Errors
+/// As decompose.
+pub fn decompose_blocked<T: Scalar>(
+    a: &mut Matrix<T>,
+    block_size: usize,
+) -> Result<(Vec<usize>, isize), Error> {
+    let (pivots, parity, _) = decompose_blocked_with(a, block_size, DecomposeOptions::default())?;
+    Ok((pivots.rows, parity))
+}
+
+///Performs decompose_blocked with the given options. Returns as decompose_with.
+///
+/// Each block of `block_size` columns is factored as a panel, by right-looking elimination of its own columns.
+/// The rows of U to the right of the panel are then solved for, and the trailing matrix below and to the right
+/// is updated by one multiplication of the panel's L by those rows of U. Most of the work is in that multiplication,
+/// which reads memory in order.
+///
+/// Complete and rook pivoting search the whole trailing matrix for every pivot, which the update of a block at a time
+/// does not keep current, so they are decomposed as by decompose_with.
+pub fn decompose_blocked_with<T: Scalar>(
+    a: &mut Matrix<T>,
+    block_size: usize,
+    options: DecomposeOptions,
+) -> Result<(Pivots, isize, Vec<usize>), Error> {
+    assert!(block_size > 0, "The block size must be at least 1.");
+    assert!(!a.is_mangled(), "Input matrix is mangled. Cannot Solve.");
+    let substitute = Substitute::new(a, options.singular_pivot);
+    if let Pivoting::Complete | Pivoting::Rook = options.pivoting {
+        return eliminate(
+            a,
+            options.pivoting,
+            &substitute,
+            None,
+            options.simd,
+            &mut NoTrace,
+        );
+    }
+    let (m, n) = (a.rows(), a.columns());
+    let square = m == n;
+
+    //Reciprocals of the largest absolute value in each row, for implicit scaling. They follow their rows when swapped.
+    let mut scales = vec![T::one(); m];
+    let implicit_scaling = options.pivoting == Pivoting::ScaledPartial;
+    for row in 0..m {
+        let max = a.row_abs_max(row).0;
+        if max == T::zero() {
+            if square {
+                return Err(Error::SingularMatrix);
+            }
+            continue;
+        }
+        if implicit_scaling {
+            scales[row] = T::one() / max;
+        }
+    }
+
+    let steps = m.min(n);
+    let mut pivots = Pivots {
+        rows: Vec::with_capacity(steps),
+        columns: (0..steps).collect(),
+    };
+    let mut parity = 1;
+    let mut substitutions = Vec::new();
+
+    '_traverse_blocks: for start in (0..steps).step_by(block_size) {
+        let end = (start + block_size).min(steps);
+
+        //Panel factorization of columns start..end, with every row swap applied across the whole of A.
+        '_factor_panel: for step in start..end {
+            let mut row_of_max = step;
+            if T::EXACT || options.pivoting == Pivoting::FirstNonZero {
+                let zero = T::zero();
+                row_of_max = (step..m)
+                    .find(|row| a.values[*row][step] != zero)
+                    .unwrap_or(step);
+            } else if options.pivoting != Pivoting::None {
+                let mut scaled_column_max = T::zero();
+                for row in step..m {
+                    let scaled = scales[row].clone() * a.values[row][step].abs();
+                    if scaled > scaled_column_max {
+                        (scaled_column_max, row_of_max) = (scaled, row);
+                    }
+                }
+            }
+            pivots.rows.push(row_of_max);
+            if row_of_max != step {
+                a.swap_rows((row_of_max, step));
+                scales.swap(row_of_max, step);
+                parity *= -1;
+            }
+            if a.values[step][step] == T::zero() {
+                if !square && (step + 1..m).all(|row| a.values[row][step] == T::zero()) {
+                    continue '_factor_panel;
+                }
+                let column = step;
+                a.values[step][step] = substitute.for_column(column)?;
+                substitutions.push(step);
+            }
+            let scale = T::one() / a.values[step][step].clone();
+            let (above, below) = a.values.split_at_mut(step + 1);
+            let pivot_row = &above[step][step + 1..end];
+            for row in below.iter_mut() {
+                row[step] *= scale.clone();
+                let factor = row[step].clone();
+                kernels::subtract_multiple(
+                    &mut row[step + 1..end],
+                    &factor,
+                    pivot_row,
+                    options.simd,
+                );
+            }
+        }
+
+        //The rows of U to the right of the panel, by forward substitution with the panel's unit lower triangle.
+        let (panel, _) = a.values.split_at_mut(end);
+        '_solve_for_u: for row in start + 1..end {
+            let (above, below) = panel.split_at_mut(row);
+            let target = &mut below[0];
+            for k in start..row {
+                let factor = target[k].clone();
+                kernels::subtract_multiple(
+                    &mut target[end..],
+                    &factor,
+                    &above[k][end..],
+                    options.simd,
+                );
+            }
+        }
+
+        //The trailing matrix, less the panel's L times the rows of U just solved for.
+        let (panel, trailing) = a.values.split_at_mut(end);
+        multiply_subtract(&panel[start..], trailing, (start, end), options.simd);
+    }
+    Ok((pivots, parity, substitutions))
+}
+
+///Subtracts L * U from the trailing rows, where L is columns start..end of `trailing`, and U is
+/// the columns from `end` on of `upper`, whose rows are start..end of A.
+///
+/// Loops over a tile of columns, then each trailing row, then each row of U, so that the innermost loop
+/// runs along contiguous memory and the tile of U is reused from cache by every trailing row.
+fn multiply_subtract<T: Scalar>(
+    upper: &[Vec<T>],
+    trailing: &mut [Vec<T>],
+    (start, end): (usize, usize),
+    simd: bool,
+) {
+    let columns = upper.first().map_or(0, |row| row.len());
+    '_traverse_tiles: for tile in (end..columns).step_by(TILE) {
+        let tile_end = (tile + TILE).min(columns);
+        for row in trailing.iter_mut() {
+            let (l, rest) = row.split_at_mut(end);
+            let rest = &mut rest[tile - end..tile_end - end];
+            for (k, u) in upper.iter().enumerate() {
+                let factor = l[start + k].clone();
+                if factor == T::zero() {
+                    continue;
+                }
+                kernels::subtract_multiple(rest, &factor, &u[tile..tile_end], simd);
+            }
+        }
+    }
+}
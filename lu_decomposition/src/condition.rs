@@ -0,0 +1,108 @@
+use crate::crout;
+use crate::sensitivity::solve_transposed_packed;
+use matrix::{Matrix, Scalar};
+
+///Estimates the reciprocal of the 1-norm condition number of A from its LU decomposition.
+///### Arguments
+/// * `a`: the packed LU decomposition of A, as returned in place by `decompose`.
+/// * `permutation`: The row-wise permutation of the variable a as a vector
+/// * `a_norm`: the 1-norm of A before it was decomposed. See Matrix::norm_one().
+///### Usage
+/// Uses Hager's algorithm, with Higham's refinements, to estimate ||A^-1|| from a handful of solves
+/// against A and its transpose. The explicit inverse is never formed.
+/// Returns a value between 0.0 and 1.0. Values near 0.0 are ill-conditioned, and values near machine epsilon are effectively singular.
+/// ```
+///    use lu_decomposition::{decompose, reciprocal_condition};
+///    let mut matrix: matrix::Matrix = matrix::Matrix::from(vec![vec![3.0, -2.0], vec![6.0, 4.0]]);
+///    let a_norm = matrix.norm_one();
+///    let (permutation, ..) = decompose(&mut matrix).unwrap();
+///    let rcond = reciprocal_condition(&matrix, &permutation, a_norm);
+///    assert!((rcond - 1.0 / 3.75).abs() < 1e-6);
+/// ```
+pub fn reciprocal_condition<T: Scalar>(a: &Matrix<T>, permutation: &[usize], a_norm: T) -> T {
+    if a_norm == T::zero() {
+        return T::zero();
+    }
+    let inverse_norm = inverse_norm_one(a, permutation);
+    if inverse_norm == T::zero() || !inverse_norm.is_finite() {
+        return T::zero();
+    }
+    (T::one() / a_norm) / inverse_norm
+}
+
+///Estimates ||A^-1|| in the 1-norm. Algorithm 4.1 of Higham, "FORTRAN codes for estimating the one-norm of a
+///real or complex matrix" (1988).
+fn inverse_norm_one<T: Scalar>(a: &Matrix<T>, permutation: &[usize]) -> T {
+    const MAXIMUM_ITERATIONS: usize = 5;
+    let n = a.rows();
+    let mut x = Matrix::from(vec![vec![T::from_f64(1.0 / n as f64)]; n]);
+    let mut estimate = T::zero();
+    let mut previous_signs: Option<Vec<T>> = None;
+
+    '_power_iteration: for iteration in 0..MAXIMUM_ITERATIONS {
+        //y = A^-1 * x
+        crout(a, permutation, &mut x);
+        let y_norm = column_norm_one(&x);
+        if iteration > 0 && y_norm <= estimate {
+            break;
+        }
+        estimate = y_norm;
+
+        //xi = sign(y). Stop when the signs repeat, since z would repeat too.
+        let signs: Vec<T> = (0..n).map(|row| sign(&x.values[row][0])).collect();
+        if previous_signs.as_ref() == Some(&signs) {
+            break;
+        }
+
+        //z = A^-T * xi
+        let mut z = Matrix::from(signs.iter().map(|sign| vec![sign.clone()]).collect());
+        solve_transposed_packed(a, permutation, &mut z);
+        let (z_max, (row_of_max, _)) = z.column_abs_max(0);
+
+        //Converged when no unit vector improves on the current x.
+        let mut z_dot_x = T::zero();
+        for row in 0..n {
+            z_dot_x += z.values[row][0].clone() * x.values[row][0].clone();
+        }
+        if iteration > 0 && z_max <= z_dot_x {
+            break;
+        }
+        previous_signs = Some(signs);
+
+        //x = e_j, where j is the location of the largest |z|.
+        x = Matrix::new(n, 1);
+        x.values[row_of_max][0] = T::one();
+    }
+
+    //Higham's alternative estimate guards against matrices that fool the power iteration.
+    let mut alternating = Matrix::new(n, 1);
+    for row in 0..n {
+        let magnitude = T::from_f64(1.0 + row as f64 / (n.max(2) - 1) as f64);
+        alternating.values[row][0] = if row % 2 == 0 { magnitude } else { -magnitude };
+    }
+    crout(a, permutation, &mut alternating);
+    let alternate_estimate =
+        T::from_f64(2.0) * column_norm_one(&alternating) / T::from_f64(3.0 * n as f64);
+
+    if alternate_estimate > estimate {
+        alternate_estimate
+    } else {
+        estimate
+    }
+}
+
+fn column_norm_one<T: Scalar>(x: &Matrix<T>) -> T {
+    let mut sum = T::zero();
+    for row in 0..x.rows() {
+        sum += x.values[row][0].abs();
+    }
+    sum
+}
+
+fn sign<T: Scalar>(value: &T) -> T {
+    if *value >= T::zero() {
+        T::one()
+    } else {
+        -T::one()
+    }
+}
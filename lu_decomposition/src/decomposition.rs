@@ -0,0 +1,234 @@
+use crate::{crout, decompose_with, reciprocal_condition, DecomposeOptions, Error};
+use matrix::trace::{NoTrace, Trace};
+use matrix::{Matrix, Scalar};
+
+///An LU decomposition of a square matrix A that owns its packed factors and its row and column permutations.
+///
+///### Usage
+/// Decompose once, then solve as many right hand sides as needed.
+/// ```
+///    use lu_decomposition::LuDecomposition;
+///    let a = matrix::Matrix::from(vec![vec![3.0, -2.0], vec![6.0, 4.0]]);
+///    let lu = LuDecomposition::new(a).unwrap();
+///    let b = matrix::Matrix::from(vec![vec![4.0], vec![-8.0]]);
+///    assert_eq!(lu.solve(&b), matrix::Matrix::from(vec![vec![0.0], vec![-2.0]]));
+///    assert_eq!(lu.determinant(), 24.0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct LuDecomposition<T = f32> {
+    packed: Matrix<T>,
+    permutation: Vec<usize>,
+    column_permutation: Vec<usize>,
+    parity: isize,
+    substitutions: Vec<usize>,
+    norm_one: T,
+    condition_warning: Option<ConditionWarning<T>>,
+}
+
+///Reported when the estimated reciprocal condition number of A falls below a caller's threshold.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConditionWarning<T = f32> {
+    pub reciprocal_condition: T,
+    pub threshold: T,
+}
+
+impl<T: Scalar> LuDecomposition<T> {
+    ///Takes ownership of A and decomposes it in place.
+    pub fn new(a: Matrix<T>) -> Result<Self, Error> {
+        LuDecomposition::with_options(a, DecomposeOptions::default())
+    }
+
+    ///Takes ownership of A and decomposes it in place with the given optimizations.
+    pub fn with_options(a: Matrix<T>, options: DecomposeOptions) -> Result<Self, Error> {
+        LuDecomposition::with_trace(a, options, &mut NoTrace)
+    }
+
+    ///Takes ownership of A and decomposes it in place, reporting each step to `trace`. See decompose_with().
+    /// Returns NotSquare for a rectangular A, which has no inverse or determinant. Use decompose_with() or RankRevealingLu.
+    pub fn with_trace(
+        mut a: Matrix<T>,
+        options: DecomposeOptions,
+        trace: &mut impl Trace<T>,
+    ) -> Result<Self, Error> {
+        let (rows, columns) = (a.rows(), a.columns());
+        if rows != columns {
+            return Err(Error::NotSquare { rows, columns });
+        }
+        let norm_one = a.norm_one();
+        let (pivots, parity, substitutions) = decompose_with(&mut a, options, trace)?;
+        Ok(LuDecomposition {
+            packed: a,
+            permutation: pivots.rows,
+            column_permutation: pivots.columns,
+            parity,
+            substitutions,
+            norm_one,
+            condition_warning: None,
+        })
+    }
+
+    ///Opts into a warning when the estimated reciprocal condition number is below `threshold`.
+    /// The estimate is made once, here. See condition_warning().
+    /// ```
+    ///    use lu_decomposition::LuDecomposition;
+    ///    let a = matrix::Matrix::from(vec![vec![1.0, 1.0], vec![1.0, 1.0001]]);
+    ///    let lu = LuDecomposition::new(a).unwrap().with_condition_warning(1.0e-3);
+    ///    assert!(lu.condition_warning().is_some());
+    /// ```
+    pub fn with_condition_warning(mut self, threshold: T) -> Self {
+        let reciprocal_condition = self.reciprocal_condition();
+        self.condition_warning = if reciprocal_condition < threshold {
+            Some(ConditionWarning {
+                reciprocal_condition,
+                threshold,
+            })
+        } else {
+            None
+        };
+        self
+    }
+
+    ///Returns a warning if the caller opted in with with_condition_warning() and A is ill-conditioned.
+    pub fn condition_warning(&self) -> Option<ConditionWarning<T>> {
+        self.condition_warning.clone()
+    }
+
+    ///Returns an estimate of the reciprocal 1-norm condition number of A. See reciprocal_condition().
+    pub fn reciprocal_condition(&self) -> T {
+        reciprocal_condition(&self.packed, &self.permutation, self.norm_one.clone())
+    }
+
+    ///Returns L and U packed into one matrix, as produced by `decompose`.
+    /// The unit diagonal of L is not stored.
+    pub fn packed(&self) -> &Matrix<T> {
+        &self.packed
+    }
+
+    ///Returns the row swaps recorded by `decompose`. Row `k` was swapped with row `permutation[k]` at step `k`.
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    ///Returns the column swaps recorded by `decompose`. Column `k` was swapped with column `column_permutation[k]` at step `k`.
+    /// Only complete and rook pivoting swap columns.
+    pub fn column_permutation(&self) -> &[usize] {
+        &self.column_permutation
+    }
+
+    ///Returns +1 for an even number of row and column swaps and -1 for an odd number.
+    pub fn parity(&self) -> isize {
+        self.parity
+    }
+
+    ///Returns the columns whose zero pivot was replaced under DecomposeOptions::singular_pivot.
+    /// Empty unless A is singular to the precision of the algorithm.
+    pub fn substitutions(&self) -> &[usize] {
+        &self.substitutions
+    }
+
+    ///Returns the size of the decomposed matrix.
+    pub fn size(&self) -> usize {
+        self.packed.rows()
+    }
+
+    ///Returns the solution X to A * X = B, leaving B untouched.
+    pub fn solve(&self, b: &Matrix<T>) -> Matrix<T> {
+        let mut x = b.clone();
+        self.solve_in_place(&mut x);
+        x
+    }
+
+    ///Returns in place of `b` the solution X to A * X = B. Panics if B has the wrong number of rows.
+    pub fn solve_in_place(&self, b: &mut Matrix<T>) {
+        assert_eq!(
+            b.rows(),
+            self.size(),
+            "Right hand side has {} rows. Expected {}.",
+            b.rows(),
+            self.size()
+        );
+        crout(&self.packed, &self.permutation, b);
+        //crout solves for Q^-1 * X. Undo the column swaps of Q in reverse to recover X.
+        for (row, swap_with) in self.column_permutation.iter().enumerate().rev() {
+            b.swap_rows((row, *swap_with));
+        }
+    }
+
+    ///Returns the inverse of A by solving against the identity, column by column.
+    pub fn inverse(&self) -> Matrix<T> {
+        let mut inverse = Matrix::identity(self.size());
+        self.solve_in_place(&mut inverse);
+        inverse
+    }
+
+    ///Returns the determinant of A. The product of the diagonal of U, multiplied by the parity.
+    ///
+    /// Overflows or underflows easily for large matrices. See log_abs_determinant().
+    pub fn determinant(&self) -> T {
+        let mut determinant = T::from_f64(self.parity as f64);
+        for row in 0..self.size() {
+            determinant *= self.packed.values[row][row].clone();
+        }
+        determinant
+    }
+
+    ///Returns the determinant as a tuple (sign, ln|det|), so that det = sign * e^(ln|det|).
+    ///
+    /// The sign is 1, -1, or 0 when a zero lies on the diagonal of U, in which case ln|det| is negative infinity.
+    /// The logarithm is taken in f64 whatever the element type.
+    pub fn log_abs_determinant(&self) -> (T, f64) {
+        let mut sign = T::from_f64(self.parity as f64);
+        let mut log_abs_determinant = 0.0;
+        for row in 0..self.size() {
+            let diagonal = &self.packed.values[row][row];
+            if *diagonal == T::zero() {
+                return (T::zero(), f64::NEG_INFINITY);
+            }
+            if *diagonal < T::zero() {
+                sign = -sign;
+            }
+            log_abs_determinant += diagonal.to_f64().abs().ln();
+        }
+        (sign, log_abs_determinant)
+    }
+
+    ///Returns the unit lower triangular factor L.
+    pub fn lower(&self) -> Matrix<T> {
+        let mut lower = Matrix::identity(self.size());
+        for row in 0..self.size() {
+            for column in 0..row {
+                lower.values[row][column] = self.packed.values[row][column].clone();
+            }
+        }
+        lower
+    }
+
+    ///Returns the upper triangular factor U.
+    pub fn upper(&self) -> Matrix<T> {
+        let mut upper = Matrix::square(self.size());
+        for row in 0..self.size() {
+            for column in row..self.size() {
+                upper.values[row][column] = self.packed.values[row][column].clone();
+            }
+        }
+        upper
+    }
+
+    ///Returns the permutation matrix P such that P * A * Q = L * U.
+    pub fn permutation_matrix(&self) -> Matrix<T> {
+        let mut p = Matrix::identity(self.size());
+        for (row, swap_with) in self.permutation.iter().enumerate() {
+            p.swap_rows((row, *swap_with));
+        }
+        p
+    }
+
+    ///Returns the permutation matrix Q such that P * A * Q = L * U. The identity unless columns were swapped.
+    pub fn column_permutation_matrix(&self) -> Matrix<T> {
+        let mut q = Matrix::identity(self.size());
+        for (column, swap_with) in self.column_permutation.iter().enumerate() {
+            q.swap_columns((column, *swap_with));
+        }
+        q
+    }
+}
@@ -1,7 +1,108 @@
-use matrix;
-#[derive(Debug)]
+use matrix::pivoting::{select_pivot, Pivoting, Pivots};
+use matrix::trace::{NoTrace, Step, Trace};
+use matrix::{kernels, Scalar};
+
+mod blocked;
+mod condition;
+mod decomposition;
+mod mixed;
+mod rank;
+mod sensitivity;
+mod verified;
+pub use blocked::{decompose_blocked, decompose_blocked_with};
+pub use condition::reciprocal_condition;
+pub use decomposition::{ConditionWarning, LuDecomposition};
+pub use mixed::{
+    solve_mixed_precision, solve_mixed_precision_with, Fallback, MixedPrecisionOptions,
+    MixedPrecisionSolution, SolvePath,
+};
+pub use rank::RankRevealingLu;
+pub use sensitivity::Adjoint;
+pub use verified::{solve_verified, solve_verified_with, VerifiedSolution, VerifiedSolveOptions};
+
+#[derive(Debug, PartialEq)]
 pub enum Error {
     SingularMatrix,
+    ///The pivot of `column` was zero, under SingularPivot::Fail. The column is of the factors, P * A * Q.
+    ZeroPivot {
+        column: usize,
+    },
+    ///A is not square, where a square matrix is required.
+    NotSquare {
+        rows: usize,
+        columns: usize,
+    },
+    ///solve_verified found no enclosure of the solution in `iterations` steps. A is singular,
+    /// or too ill conditioned for its inverse in f64.
+    NotVerified {
+        iterations: usize,
+    },
+}
+
+///The optimizations decompose may apply. Each flag guards the code it enables with an if statement
+///of the same name, so a word search for the flag finds every line it affects.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DecomposeOptions {
+    ///How each pivot is chosen. Scaled partial pivoting, the choice of the book, chooses pivots as if every row
+    /// were first scaled so its largest element is 1 (implicit scaling).
+    /// None, Partial and ScaledPartial use Crout's algorithm. Complete and Rook need the whole of the remaining
+    /// submatrix at each step, so they eliminate it row by row instead, swapping columns as well as rows.
+    pub pivoting: Pivoting,
+    ///What to do with a pivot that is exactly zero. The book substitutes TINY and carries on.
+    pub singular_pivot: SingularPivot,
+    ///Sums and eliminates with vectorized kernels. The summations of Crout's algorithm may round differently,
+    /// so it defaults to off. See matrix::kernels.
+    pub simd: bool,
+}
+
+impl Default for DecomposeOptions {
+    fn default() -> Self {
+        DecomposeOptions {
+            pivoting: Pivoting::ScaledPartial,
+            singular_pivot: SingularPivot::Tiny(None),
+            simd: false,
+        }
+    }
+}
+
+///How decompose treats a pivot that is exactly zero, which makes A singular to the precision of the algorithm.
+/// Every substitution is reported, by the column of its pivot, in the result of decompose_with.
+/// Exact types, such as Rational, always fail, as their zero pivots are those of singular matrices.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SingularPivot {
+    ///Stops with Error::ZeroPivot, naming the column.
+    Fail,
+    ///Substitutes TINY for the pivot and carries on. For some applications on singular matrices it is desirable
+    /// to go on to a solution. None substitutes epsilon squared of the element type, about 1.4e-14 for f32
+    /// and 4.9e-32 for f64.
+    Tiny(Option<f64>),
+    ///Substitutes the given multiple of the 1-norm of A for the pivot, so the perturbation is relative to the
+    /// size of A. None takes epsilon of the element type, the size of a rounding error.
+    Perturb(Option<f64>),
+}
+
+///The optimizations crout may apply. Each flag guards the code it enables with an if statement
+///of the same name, so a word search for the flag finds every line it affects.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CroutOptions {
+    ///Skips the forward substitution of the leading zeros in each column of B.
+    /// Pays off for sparse right hand sides, such as the columns of the identity when computing an inverse.
+    pub skip_leading_zeros: bool,
+    ///The number of threads the columns of B are split across. Each column is solved exactly as on one thread,
+    /// so the solution is the same. Only the start of a solve on more than one thread is reported to the trace.
+    pub threads: usize,
+    ///Substitutes with vectorized kernels. The sums may round differently, so it defaults to off. See matrix::kernels.
+    pub simd: bool,
+}
+
+impl Default for CroutOptions {
+    fn default() -> Self {
+        CroutOptions {
+            skip_leading_zeros: true,
+            threads: 1,
+            simd: false,
+        }
+    }
 }
 
 ///Performs LU decomposition in place on a matrix A
@@ -19,43 +120,86 @@ pub enum Error {
 ///    assert!(result.is_ok());
 ///    assert_eq!(matrix, solution);
 /// ```
-pub fn decompose(a: &mut crate::matrix::Matrix) -> Result<(Vec<usize>, isize), Error> {
-    let mut scalars = vec![1.0; a.values.len()];
+pub fn decompose<T: Scalar>(a: &mut matrix::Matrix<T>) -> Result<(Vec<usize>, isize), Error> {
+    let (pivots, parity, _) = decompose_with(a, DecomposeOptions::default(), &mut NoTrace)?;
+    Ok((pivots.rows, parity))
+}
+
+///Performs decompose with the given optimizations, reporting each summation, pivot selection, row swap
+///and column scaling to `trace`.
+///
+/// Returns where each pivot was found, the parity of the row and column swaps together,
+/// and the columns whose zero pivot was replaced under options.singular_pivot.
+/// Row k was swapped with row rows[k], and column k with column columns[k], at step k, so that P * A * Q = L * U.
+/// Pass the rows to crout, then undo the column swaps in reverse order on the solution. LuDecomposition does both.
+///
+/// A may be rectangular, m x n. Its min(m, n) steps leave the m x min(m, n) unit lower trapezoidal L below the diagonal,
+/// and the min(m, n) x n upper trapezoidal U on and above it. See RankRevealingLu to stop at the numerical rank.
+pub fn decompose_with<T: Scalar>(
+    a: &mut matrix::Matrix<T>,
+    options: DecomposeOptions,
+    trace: &mut impl Trace<T>,
+) -> Result<(Pivots, isize, Vec<usize>), Error> {
+    let substitute = Substitute::new(a, options.singular_pivot);
+    let square = a.rows() == a.columns();
+    if !square || matches!(options.pivoting, Pivoting::Complete | Pivoting::Rook) {
+        return eliminate(a, options.pivoting, &substitute, None, options.simd, trace);
+    }
+    trace.start(a, None);
+    let mut scalars = vec![T::one(); a.values.len()];
     let mut operations: Vec<usize> = vec![0; a.values.len()];
     let mut parity = 1;
+    let mut substitutions = Vec::new();
 
     //Record implicit scaling
+    let implicit_scaling = options.pivoting == Pivoting::ScaledPartial;
     for row in 0..a.rows() {
         let max = a.row_abs_max(row).0;
-        if max == 0.0 {
+        if max == T::zero() {
             return Err(Error::SingularMatrix);
         }
-        scalars[row] /= max;
+        if implicit_scaling {
+            scalars[row] /= max;
+        }
     }
 
     assert!(scalars.len() == a.rows());
     assert!(!a.is_mangled(), "Input matrix is mangled. Cannot Solve.");
 
+    //Exact types take the first non-zero pivot in the column, below the diagonal.
+    let first_non_zero = T::EXACT || options.pivoting == Pivoting::FirstNonZero;
+
     //Crout's algorithm
     //2.3.12
     for column in 0..a.columns() {
-        let (mut scaled_column_max, mut row_of_max): (f32, usize) = (0.00, column);
+        let (mut scaled_column_max, mut row_of_max): (T, usize) = (T::zero(), column);
+        //The column as summed so far, gathered into a slice for the summation kernel.
+        let mut gathered: Vec<T> = Vec::with_capacity(a.rows());
 
         'summations: for row in 0..a.columns() {
-            let mut sum = a.values[row][column];
-
             //Summation: According to figures 2.3.8, 2.3.9, and 2.3.10 pg 46, The end of the loop is minimum of row and column.
-            for k in 0..row.min(column) {
-                sum -= a.values[row][k] * a.values[k][column];
+            let terms = row.min(column);
+            let initial = a.values[row][column].clone();
+            let (l, u) = (&a.values[row][..terms], &gathered[..terms]);
+            let sum = kernels::dot_subtract(initial, l, u, options.simd);
+            a.values[row][column] = sum.clone();
+            gathered.push(sum.clone());
+            if row.min(column) > 0 {
+                trace.record(Step::Substitute { row, column }, a, None);
             }
-            a.values[row][column] = sum;
 
             //Row-only Pivot Tracking: Track largest alpha on or below the diagonal.
             let above_diagonal = row < column;
             if above_diagonal {
                 continue 'summations;
+            } else if first_non_zero {
+                if scaled_column_max == T::zero() && sum != T::zero() {
+                    (scaled_column_max, row_of_max) = (T::one(), row);
+                }
+            } else if options.pivoting == Pivoting::None {
+                continue 'summations;
             } else {
-                let scaled_sum = scalars[row] * sum.abs();
+                let scaled_sum = scalars[row].clone() * sum.abs();
                 if scaled_sum > scaled_column_max {
                     (scaled_column_max, row_of_max) = (scaled_sum, row);
                 }
@@ -63,37 +207,205 @@ pub fn decompose(a: &mut crate::matrix::Matrix) -> Result<(Vec<usize>, isize), E
         }
 
         //Pivot if necessary.
+        trace.record(
+            Step::SelectPivot {
+                row: row_of_max,
+                column,
+            },
+            a,
+            None,
+        );
         let on_diagonal = column == row_of_max;
         if !on_diagonal {
             a.swap_rows((row_of_max, column));
             scalars.swap(row_of_max, column);
             parity *= -1; //Flip parity for odd or even number of swaps.
+            trace.record(
+                Step::SwapRows {
+                    rows: (row_of_max, column),
+                },
+                a,
+                None,
+            );
         }
         operations[column] = row_of_max;
 
-        //??TINY
-        let pivot_element = a.values[column][column];
-        if pivot_element == 0.0 {
-            a.values[column][column] = 1.0f32.powi(-40);
-            /*If the pivot element is zero the matrix is singular (at least to the precision
-                of the algorithm). For some applications on singular matrices, it is desirable to substitute TINY(an extremely small number) for zero.
-            */
+        let pivot_element = a.values[column][column].clone();
+        if pivot_element == T::zero() {
+            a.values[column][column] = substitute.for_column(column)?;
+            substitutions.push(column);
         }
         //Divide alphas (below the diagonal) by the pivot_element.
+        let scale = T::one() / a.values[column][column].clone();
         '_scale_column: for row in column + 1..a.columns() {
-            a.values[row][column] *= 1.0 / a.values[column][column]
+            a.values[row][column] *= scale.clone();
+        }
+        if column + 1 < a.columns() {
+            trace.record(Step::ScaleColumn { column, scale }, a, None);
+        }
+    }
+    let pivots = Pivots {
+        rows: operations,
+        columns: (0..a.columns()).collect(),
+    };
+    Ok((pivots, parity, substitutions))
+}
+
+///Decomposes the m x n matrix A by Gaussian elimination of the remaining submatrix at each step, for
+/// complete and rook pivoting, and for rectangular matrices with any strategy.
+/// Produces the same packed L and U as Crout's algorithm, of the rows and columns in pivot order.
+///
+/// Stops after min(m, n) steps, or at the first pivot no larger than `threshold` in absolute value, if given.
+/// Only the steps taken are returned in the pivots.
+/// A zero pivot of a rectangular matrix is left in place when there is nothing below it to eliminate.
+/// Any other zero pivot is replaced by `substitute`, or is an error if its policy says so.
+pub(crate) fn eliminate<T: Scalar>(
+    a: &mut matrix::Matrix<T>,
+    pivoting: Pivoting,
+    substitute: &Substitute<T>,
+    threshold: Option<T>,
+    simd: bool,
+    trace: &mut impl Trace<T>,
+) -> Result<(Pivots, isize, Vec<usize>), Error> {
+    trace.start(a, None);
+    assert!(!a.is_mangled(), "Input matrix is mangled. Cannot Solve.");
+    let (m, n) = (a.rows(), a.columns());
+    let square = m == n;
+    if square && threshold.is_none() {
+        for row in 0..m {
+            if a.row_abs_max(row).0 == T::zero() {
+                return Err(Error::SingularMatrix);
+            }
+        }
+    }
+    //Reciprocals of the largest absolute value in each row, for scaled partial pivoting. They follow their rows when swapped.
+    let mut scales = vec![T::one(); m];
+    if pivoting == Pivoting::ScaledPartial {
+        for row in 0..m {
+            let max = a.row_abs_max(row).0;
+            if max != T::zero() {
+                scales[row] = T::one() / max;
+            }
+        }
+    }
+    let mut pivots = Pivots::default();
+    let mut parity = 1;
+    let mut substitutions = Vec::new();
+
+    '_main: for step in 0..m.min(n) {
+        let rows: Vec<usize> = (step..m).collect();
+        let columns: Vec<usize> = (step..n).collect();
+        let location = select_pivot(a, &rows, &columns, pivoting, &scales);
+        if let Some(threshold) = &threshold {
+            match &location {
+                Some((row, column)) if a.values[*row][*column].abs() > *threshold => {}
+                _ => break '_main,
+            }
+        }
+        let (row, column) = location.unwrap_or((step, step));
+        pivots.rows.push(row);
+        pivots.columns.push(column);
+        trace.record(Step::SelectPivot { row, column }, a, None);
+        if row != step {
+            a.swap_rows((row, step));
+            scales.swap(row, step);
+            parity *= -1;
+            trace.record(Step::SwapRows { rows: (row, step) }, a, None);
+        }
+        if column != step {
+            a.swap_columns((column, step));
+            parity *= -1;
+            let columns = (column, step);
+            trace.record(Step::SwapColumns { columns }, a, None);
+        }
+        if a.values[step][step] == T::zero() {
+            if !square && (step + 1..m).all(|row| a.values[row][step] == T::zero()) {
+                continue '_main;
+            }
+            let column = step;
+            a.values[step][step] = substitute.for_column(column)?;
+            substitutions.push(step);
+        }
+
+        //The column below the pivot becomes the column of L.
+        let scale = T::one() / a.values[step][step].clone();
+        '_scale_column: for row in step + 1..m {
+            a.values[row][step] *= scale.clone();
+        }
+        if step + 1 < m {
+            trace.record(
+                Step::ScaleColumn {
+                    column: step,
+                    scale,
+                },
+                a,
+                None,
+            );
+        }
+
+        //The rows below the pivot become the remaining submatrix, the Schur complement.
+        '_traverse_submatrix: for row in step + 1..m {
+            let factor = a.values[row][step].clone();
+            let (above, below) = a.values.split_at_mut(row);
+            let source = &above[step][step + 1..];
+            kernels::subtract_multiple(&mut below[0][step + 1..], &factor, source, simd);
+            let (target, source) = (row, step);
+            trace.record(
+                Step::EliminateRow {
+                    target,
+                    source,
+                    factor,
+                },
+                a,
+                None,
+            );
+        }
+    }
+    Ok((pivots, parity, substitutions))
+}
+
+///The value decompose substitutes for a zero pivot under a SingularPivot policy. It is worked out only when a zero
+/// pivot is found, so a decomposition without one does no extra arithmetic. Perturb measures A before decomposition
+/// begins, since elimination overwrites it. A zero pivot of an exact type is always an error,
+/// since its only zero pivots are those of a singular matrix.
+pub(crate) struct Substitute<T> {
+    policy: SingularPivot,
+    norm_one: Option<T>,
+}
+
+impl<T: Scalar> Substitute<T> {
+    pub(crate) fn new(a: &matrix::Matrix<T>, policy: SingularPivot) -> Self {
+        let norm_one = match policy {
+            SingularPivot::Perturb(_) if !T::EXACT => Some(a.norm_one()),
+            _ => None,
+        };
+        Substitute { policy, norm_one }
+    }
+
+    ///Returns the value to substitute for the zero pivot of `column`, or ZeroPivot if a zero pivot is an error.
+    pub(crate) fn for_column(&self, column: usize) -> Result<T, Error> {
+        if T::EXACT {
+            return Err(Error::ZeroPivot { column });
+        }
+        match (self.policy, &self.norm_one) {
+            (SingularPivot::Tiny(Some(tiny)), _) => Ok(T::from_f64(tiny)),
+            (SingularPivot::Tiny(None), _) => Ok(T::epsilon() * T::epsilon()),
+            (SingularPivot::Perturb(relative), Some(norm_one)) => {
+                Ok(relative.map_or_else(T::epsilon, T::from_f64) * norm_one.clone())
+            }
+            _ => Err(Error::ZeroPivot { column }),
         }
     }
-    Ok((operations, parity))
 }
 
 ///Performs Crouts algorithm on an LU decomposed matrix, A,  and a right hand side matrix B.
 ///### Arguments
-///     a: a left hand side decomposed matrix.
-///     permutation: The row-wise permutation of the variable a as a vector
-///     b: right hand side matrix b.
+/// * `a`: a left hand side decomposed matrix.
+/// * `permutation`: The row-wise permutation of the variable a as a vector
+/// * `b`: right hand side matrix b.
 ///### Usage
-/// Returns in place of `b` a solution vector that is not permutated.
+/// Returns in place of `b` the solution vectors, one per column. Each column of `b` is permuted
+/// as it is solved, so the solution is in the original order of the unknowns.
 /// Does not check for compatibility, or mangled matrix. Panics on out of bounds.
 /// ```
 /// //Calculate the inverse
@@ -110,28 +422,108 @@ pub fn decompose(a: &mut crate::matrix::Matrix) -> Result<(Vec<usize>, isize), E
 ///    crout(&matrix, &permutation, &mut matrix_b);
 ///    assert_eq!(matrix_b, solution);
 /// ```
-pub fn crout(a: &matrix::Matrix, permutation: &Vec<usize>, b: &mut matrix::Matrix) {
-    forward_substitution(a, permutation, b);
-    backward_substitution(a, b);
-    //Reverse Permutation
-    for row in (0..b.rows()).rev() {
-        b.swap_rows((row, permutation[row]));
+pub fn crout<T: Scalar>(a: &matrix::Matrix<T>, permutation: &[usize], b: &mut matrix::Matrix<T>) {
+    crout_with(a, permutation, b, CroutOptions::default(), &mut NoTrace);
+}
+
+///Performs crout with the given optimizations, reporting each swap and substitution in `b` to `trace`.
+pub fn crout_with<T: Scalar>(
+    a: &matrix::Matrix<T>,
+    permutation: &[usize],
+    b: &mut matrix::Matrix<T>,
+    options: CroutOptions,
+    trace: &mut impl Trace<T>,
+) {
+    trace.start(a, Some(b));
+    if options.threads > 1 && b.columns_unchecked() > 1 {
+        solve_in_parallel(a, permutation, b, options);
+        return;
+    }
+    forward_substitution(a, permutation, b, options, trace);
+    backward_substitution(a, b, options, trace);
+}
+
+///Splits the columns of B into one block per thread, solves each block on its own thread, and writes the solutions back.
+fn solve_in_parallel<T: Scalar>(
+    a: &matrix::Matrix<T>,
+    permutation: &[usize],
+    b: &mut matrix::Matrix<T>,
+    options: CroutOptions,
+) {
+    let columns = b.columns_unchecked();
+    let per_thread = columns.div_ceil(options.threads.min(columns));
+    let serial = CroutOptions {
+        threads: 1,
+        ..options
+    };
+    let blocks: Vec<(usize, matrix::Matrix<T>)> = (0..columns)
+        .step_by(per_thread)
+        .map(|start| {
+            let end = (start + per_thread).min(columns);
+            let values = b.values.iter().map(|row| row[start..end].to_vec());
+            (start, matrix::Matrix::from(values.collect::<Vec<_>>()))
+        })
+        .collect();
+    let solved = std::thread::scope(|scope| {
+        let handles: Vec<_> = blocks
+            .into_iter()
+            .map(|(start, mut block)| {
+                scope.spawn(move || {
+                    crout_with(a, permutation, &mut block, serial, &mut NoTrace);
+                    (start, block)
+                })
+            })
+            .collect();
+        let solved: Vec<(usize, matrix::Matrix<T>)> = handles
+            .into_iter()
+            .map(|handle| handle.join().expect("a solver thread panicked"))
+            .collect();
+        solved
+    });
+    '_write_back: for (start, block) in solved {
+        for (row, values) in block.values.into_iter().enumerate() {
+            for (offset, value) in values.into_iter().enumerate() {
+                b.values[row][start + offset] = value;
+            }
+        }
     }
 }
 
 ///Performs the forward substitution step of Crout\'s algorithm. Returns in place of `b`
-///a solution vector that is permutated by A's permutation.
-fn forward_substitution(a: &matrix::Matrix, permutation: &Vec<usize>, b: &mut matrix::Matrix) {
+///an intermediate solution vector that is permutated by A's permutation.
+fn forward_substitution<T: Scalar>(
+    a: &matrix::Matrix<T>,
+    permutation: &[usize],
+    b: &mut matrix::Matrix<T>,
+    options: CroutOptions,
+    trace: &mut impl Trace<T>,
+) {
     for column in 0..b.columns_unchecked() {
+        //The column as solved so far, gathered into a slice for the summation kernel.
+        let mut gathered = vec![T::zero(); a.rows()];
         let mut non_zero_rhs_encountered = false; //Optimization for sparse right hand side with many leading zero's
         for row in 0..a.rows() {
-            b.swap_rows((row, permutation[row])); //Permute b like a to line up solutions with the equations they solve.
-            if non_zero_rhs_encountered {
-                for k in 0..row {
-                    b.values[row][column] -= a.values[row][k] * b.values[k][column];
-                }
-            } else {
-                non_zero_rhs_encountered = b.values[row][column] != 0.0;
+            //Permute this column of b like a to line up solutions with the equations they solve.
+            let swap_with = permutation[row];
+            let temp = b.values[row][column].clone();
+            b.values[row][column] = b.values[swap_with][column].clone();
+            b.values[swap_with][column] = temp;
+            if row != swap_with {
+                let rows = (row, swap_with);
+                trace.record(Step::SwapElements { rows, column }, a, Some(b));
+            }
+            if options.skip_leading_zeros && !non_zero_rhs_encountered {
+                //Every element above is zero, so there is nothing to subtract.
+                non_zero_rhs_encountered = b.values[row][column] != T::zero();
+                gathered[row] = b.values[row][column].clone();
+                continue;
+            }
+            let (initial, l) = (b.values[row][column].clone(), &a.values[row][..row]);
+            b.values[row][column] =
+                kernels::dot_subtract(initial, l, &gathered[..row], options.simd);
+            gathered[row] = b.values[row][column].clone();
+            if row > 0 {
+                trace.record(Step::Substitute { row, column }, a, Some(b));
             }
         }
     }
@@ -139,19 +531,23 @@ fn forward_substitution(a: &matrix::Matrix, permutation: &Vec<usize>, b: &mut ma
 
 ///Performs the backwards substitution step of Crout's algorithm. Returns in place of `b`
 ///a solution vector that is not permutated. Primarily for testing. Use crout when hoping to apply Crout's.
-fn backward_substitution(a: &matrix::Matrix, b: &mut matrix::Matrix) {
+fn backward_substitution<T: Scalar>(
+    a: &matrix::Matrix<T>,
+    b: &mut matrix::Matrix<T>,
+    options: CroutOptions,
+    trace: &mut impl Trace<T>,
+) {
     //Allow for multi dimensional matrices solved one column at a time.
 
     for column in 0..b.columns_unchecked() {
+        //The column as solved so far, gathered into a slice for the summation kernel.
+        let mut gathered = vec![T::zero(); a.rows()];
         for row in (0..a.rows()).rev() {
-            let mut sum = b.values[row][column];
-            for k in row..a.rows() {
-                if row == k {
-                    continue;
-                }
-                sum -= a.values[row][k] * b.values[k][column];
-            }
-            b.values[row][column] = sum / a.values[row][row];
+            let (initial, u) = (b.values[row][column].clone(), &a.values[row][row + 1..]);
+            let sum = kernels::dot_subtract(initial, u, &gathered[row + 1..], options.simd);
+            b.values[row][column] = sum / a.values[row][row].clone();
+            gathered[row] = b.values[row][column].clone();
+            trace.record(Step::Substitute { row, column }, a, Some(b));
         }
     }
 }
@@ -168,7 +564,13 @@ fn forward_substitution_3x1() {
     let mut b = matrix::Matrix::from(vec![vec![19.5], vec![6.0], vec![48.0]]);
 
     let permutated_solution = matrix::Matrix::from(vec![vec![6.0], vec![96.0], vec![7.5]]);
-    forward_substitution(&matrix, &permutation, &mut b);
+    forward_substitution(
+        &matrix,
+        &permutation,
+        &mut b,
+        CroutOptions::default(),
+        &mut NoTrace,
+    );
     assert_eq!(b, permutated_solution);
 }
 
@@ -183,8 +585,14 @@ fn backward_substitution_3x1() {
     let (permutation, ..) = result.unwrap();
     let mut b = matrix::Matrix::from(vec![vec![19.5], vec![6.0], vec![48.0]]);
     let solution = matrix::Matrix::from(vec![vec![0.0], vec![0.25], vec![1.0]]);
-    forward_substitution(&matrix, &permutation, &mut b);
-    backward_substitution(&matrix, &mut b);
+    forward_substitution(
+        &matrix,
+        &permutation,
+        &mut b,
+        CroutOptions::default(),
+        &mut NoTrace,
+    );
+    backward_substitution(&matrix, &mut b, CroutOptions::default(), &mut NoTrace);
     assert_eq!(b, solution);
 }
 
@@ -198,7 +606,13 @@ fn forward_substitution_2x1() {
     let result = decompose(&mut matrix);
     let (permutation, ..) = result.unwrap();
 
-    forward_substitution(&matrix, &permutation, &mut matrix_b);
+    forward_substitution(
+        &matrix,
+        &permutation,
+        &mut matrix_b,
+        CroutOptions::default(),
+        &mut NoTrace,
+    );
     assert_eq!(matrix_b, solution);
 }
 
@@ -211,8 +625,19 @@ fn backward_substitution_2x1() {
     let result = decompose(&mut matrix);
     let (permutation, ..) = result.unwrap();
 
-    forward_substitution(&matrix, &permutation, &mut matrix_b);
-    backward_substitution(&matrix, &mut matrix_b);
+    forward_substitution(
+        &matrix,
+        &permutation,
+        &mut matrix_b,
+        CroutOptions::default(),
+        &mut NoTrace,
+    );
+    backward_substitution(
+        &matrix,
+        &mut matrix_b,
+        CroutOptions::default(),
+        &mut NoTrace,
+    );
     assert_eq!(matrix_b, solution);
 }
 
@@ -224,7 +649,13 @@ fn forward_substitution_2x2() {
     let mut b = matrix::Matrix::identity(2);
 
     let permutated_solution = matrix::Matrix::from(vec![vec![1.0, 0.0], vec![-2.0, 1.0]]);
-    forward_substitution(&matrix, &permutation, &mut b);
+    forward_substitution(
+        &matrix,
+        &permutation,
+        &mut b,
+        CroutOptions::default(),
+        &mut NoTrace,
+    );
     assert_eq!(b, permutated_solution);
 }
 #[test]
@@ -238,8 +669,14 @@ fn backward_substitution_2x2() {
         vec![1.0 / 6.0, 1.0 / 12.0],
         vec![-1.0 / 4.0, 1.0 / 8.0],
     ]);
-    forward_substitution(&matrix, &permutation, &mut b);
-    backward_substitution(&matrix, &mut b);
+    forward_substitution(
+        &matrix,
+        &permutation,
+        &mut b,
+        CroutOptions::default(),
+        &mut NoTrace,
+    );
+    backward_substitution(&matrix, &mut b, CroutOptions::default(), &mut NoTrace);
     assert_eq!(b, permutated_solution);
 }
 
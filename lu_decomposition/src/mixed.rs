@@ -0,0 +1,171 @@
+use crate::{DecomposeOptions, Error, LuDecomposition, SingularPivot};
+use matrix::verify::verify;
+use matrix::{Matrix, Scalar};
+
+///The choices solve_mixed_precision may make.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MixedPrecisionOptions {
+    ///The most refinement steps taken with the f32 factors before falling back to an f64 factorization.
+    pub max_iterations: usize,
+    ///The normwise backward error at which refinement stops. None takes sqrt(n) times f64 epsilon, as LAPACK's dsgesv does.
+    pub tolerance: Option<f64>,
+    ///How A is decomposed, in f32 and, on fallback, in f64. A zero pivot always stops the f32 factorization.
+    pub decompose: DecomposeOptions,
+}
+
+impl Default for MixedPrecisionOptions {
+    fn default() -> Self {
+        MixedPrecisionOptions {
+            max_iterations: 30,
+            tolerance: None,
+            decompose: DecomposeOptions::default(),
+        }
+    }
+}
+
+///How solve_mixed_precision reached its solution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SolvePath {
+    ///A was factored in f32, and the solution refined in f64 in `iterations` steps.
+    Refined { iterations: usize },
+    ///A was factored in f64, because the f32 factors could not give an f64 solution.
+    Double { reason: Fallback },
+}
+
+///Why solve_mixed_precision fell back to an f64 factorization.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fallback {
+    ///An element of A or B, or of a residual during refinement, is too large for f32.
+    OutOfRange,
+    ///A pivot of the f32 factorization was zero.
+    SingularInSinglePrecision,
+    ///Refinement stopped improving, or ran out of iterations. A is too ill conditioned for f32 factors,
+    /// roughly when cond(A) exceeds 1 / f32 epsilon, about 10^7.
+    NoConvergence,
+}
+
+///The solution of solve_mixed_precision, and how it was reached.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MixedPrecisionSolution {
+    pub solution: Matrix<f64>,
+    pub path: SolvePath,
+    ///The normwise backward error of the solution. See matrix::verify.
+    pub backward_error: f64,
+}
+
+///Solves A * X = B to f64 accuracy with f32 factors, by iterative refinement.
+///
+///### Usage
+/// Decomposes A in f32, the most expensive step, at single precision speed. Each step of refinement computes
+/// the residual R = B - A * X in f64, solves A * D = R with the f32 factors, and adds the correction D to X.
+/// Falls back to an f64 factorization if refinement does not reach the tolerance, and reports which path was taken.
+/// ```
+///    use lu_decomposition::{solve_mixed_precision, SolvePath};
+///    let a = matrix::Matrix::from(vec![vec![4.0, 1.0], vec![1.0, 3.0]]);
+///    let b = matrix::Matrix::from(vec![vec![1.0], vec![2.0]]);
+///    let result = solve_mixed_precision(&a, &b).unwrap();
+///    assert!(matches!(result.path, SolvePath::Refined { .. }));
+///    assert!((result.solution.values[0][0] - 1.0 / 11.0).abs() < 1e-15);
+///    assert!((result.solution.values[1][0] - 7.0 / 11.0).abs() < 1e-15);
+/// ```
+///### Errors
+/// `NotSquare` for a rectangular A. `ZeroPivot` if A is singular in f64 too.
+pub fn solve_mixed_precision(
+    a: &Matrix<f64>,
+    b: &Matrix<f64>,
+) -> Result<MixedPrecisionSolution, Error> {
+    solve_mixed_precision_with(a, b, MixedPrecisionOptions::default())
+}
+
+///Performs solve_mixed_precision with the given options.
+pub fn solve_mixed_precision_with(
+    a: &Matrix<f64>,
+    b: &Matrix<f64>,
+    options: MixedPrecisionOptions,
+) -> Result<MixedPrecisionSolution, Error> {
+    let (rows, columns) = (a.rows(), a.columns());
+    if rows != columns {
+        return Err(Error::NotSquare { rows, columns });
+    }
+    let tolerance = options
+        .tolerance
+        .unwrap_or_else(|| (rows as f64).sqrt() * f64::EPSILON);
+    let decompose = DecomposeOptions {
+        singular_pivot: SingularPivot::Fail,
+        ..options.decompose
+    };
+
+    let reason = '_refine: {
+        let (single_a, single_b) = match (single(a), single(b)) {
+            (Some(single_a), Some(single_b)) => (single_a, single_b),
+            _ => break '_refine Fallback::OutOfRange,
+        };
+        let lu = match LuDecomposition::with_options(single_a, decompose) {
+            Ok(lu) => lu,
+            Err(_) => break '_refine Fallback::SingularInSinglePrecision,
+        };
+        let mut solution = double(&lu.solve(&single_b));
+        let mut previous_error = f64::INFINITY;
+        for iterations in 0..=options.max_iterations {
+            let verification = verify(a, &solution, b, None);
+            let backward_error = verification.normwise_backward_error;
+            if backward_error <= tolerance {
+                return Ok(MixedPrecisionSolution {
+                    solution,
+                    path: SolvePath::Refined { iterations },
+                    backward_error,
+                });
+            }
+            //Refinement that does not reduce the error will not reach the tolerance.
+            if backward_error >= previous_error || iterations == options.max_iterations {
+                break;
+            }
+            previous_error = backward_error;
+            let residual = match single(&verification.residual) {
+                Some(residual) => residual,
+                None => break '_refine Fallback::OutOfRange,
+            };
+            let correction = double(&lu.solve(&residual));
+            for (row, corrections) in correction.values.iter().enumerate() {
+                for (column, correction) in corrections.iter().enumerate() {
+                    solution.values[row][column] += correction;
+                }
+            }
+        }
+        Fallback::NoConvergence
+    };
+
+    let lu = LuDecomposition::with_options(a.clone(), decompose)?;
+    let solution = lu.solve(b);
+    let backward_error = verify(a, &solution, b, None).normwise_backward_error;
+    Ok(MixedPrecisionSolution {
+        solution,
+        path: SolvePath::Double { reason },
+        backward_error,
+    })
+}
+
+///Rounds to f32. Returns None if an element is too large for f32.
+fn single(matrix: &Matrix<f64>) -> Option<Matrix<f32>> {
+    let single = convert(matrix, |value| *value as f32);
+    let finite = single
+        .values()
+        .iter()
+        .flatten()
+        .all(|value| value.is_finite());
+    finite.then_some(single)
+}
+
+fn double(matrix: &Matrix<f32>) -> Matrix<f64> {
+    convert(matrix, |value| *value as f64)
+}
+
+fn convert<S: Scalar, T: Scalar>(matrix: &Matrix<S>, element: impl Fn(&S) -> T) -> Matrix<T> {
+    let mut converted = Matrix::new(matrix.rows(), matrix.columns());
+    for (row, values) in matrix.values().iter().enumerate() {
+        for (column, value) in values.iter().enumerate() {
+            converted.values[row][column] = element(value);
+        }
+    }
+    converted
+}
@@ -0,0 +1,145 @@
+use crate::{eliminate, SingularPivot, Substitute};
+use matrix::pivoting::{Pivoting, Pivots};
+use matrix::trace::{NoTrace, Trace};
+use matrix::{Matrix, Scalar};
+
+///A rank-revealing LU decomposition of an m x n matrix A by complete pivoting, P * A * Q = L * U,
+/// stopped at the numerical rank.
+///
+///### Usage
+/// Elimination stops at the first pivot no larger than `tolerance` times the largest absolute element of A.
+/// The number of steps taken is the numerical rank r. The first r rows and columns in pivot order are independent,
+/// and the rows after them are redundant: each is, to the tolerance, a combination of the independent rows.
+/// ```
+///    use lu_decomposition::RankRevealingLu;
+///    let a = matrix::Matrix::from(vec![
+///        vec![1.0, 2.0, 3.0],
+///        vec![2.0, 4.0, 6.0],
+///        vec![1.0, 0.0, 1.0],
+///    ]);
+///    let lu = RankRevealingLu::new(a, 1.0e-12);
+///    assert_eq!(lu.rank(), 2);
+///    assert_eq!(lu.redundant_rows(), vec![0]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RankRevealingLu<T = f32> {
+    packed: Matrix<T>,
+    pivots: Pivots,
+    rank: usize,
+}
+
+impl<T: Scalar> RankRevealingLu<T> {
+    ///Takes ownership of A and decomposes it in place.
+    pub fn new(a: Matrix<T>, tolerance: T) -> Self {
+        RankRevealingLu::with_trace(a, tolerance, &mut NoTrace)
+    }
+
+    ///Takes ownership of A and decomposes it in place, reporting each step to `trace`.
+    pub fn with_trace(mut a: Matrix<T>, tolerance: T, trace: &mut impl Trace<T>) -> Self {
+        let mut largest = T::zero();
+        for row in 0..a.rows() {
+            let max = a.row_abs_max(row).0;
+            if max > largest {
+                largest = max;
+            }
+        }
+        let threshold = tolerance * largest;
+        //A zero pivot never exceeds the threshold, so elimination stops before one can be reached.
+        let substitute = Substitute::new(&a, SingularPivot::Fail);
+        let (pivots, ..) = eliminate(
+            &mut a,
+            Pivoting::Complete,
+            &substitute,
+            Some(threshold),
+            false,
+            trace,
+        )
+        .expect("complete pivoting stops at the numerical rank");
+        let rank = pivots.rows.len();
+        RankRevealingLu {
+            packed: a,
+            pivots,
+            rank,
+        }
+    }
+
+    ///The numerical rank of A.
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+
+    ///Where each of the `rank` pivots was found. Row k was swapped with row rows[k], and column k with
+    /// column columns[k], at step k.
+    pub fn pivots(&self) -> &Pivots {
+        &self.pivots
+    }
+
+    ///Returns L and U packed into one m x n matrix, with the unit diagonal of L implied.
+    /// The rows and columns after the rank hold the remaining submatrix, whose elements are all within the tolerance.
+    pub fn packed(&self) -> &Matrix<T> {
+        &self.packed
+    }
+
+    ///Returns the rows of A in pivot order. Row k of P * A is row row_order()[k] of A.
+    pub fn row_order(&self) -> Vec<usize> {
+        order(self.packed.rows(), &self.pivots.rows)
+    }
+
+    ///Returns the columns of A in pivot order. Column k of A * Q is column column_order()[k] of A.
+    pub fn column_order(&self) -> Vec<usize> {
+        order(self.packed.columns(), &self.pivots.columns)
+    }
+
+    ///Returns the rows of A that are independent of each other, in pivot order.
+    pub fn independent_rows(&self) -> Vec<usize> {
+        self.row_order()[..self.rank].to_vec()
+    }
+
+    ///Returns the rows of A that are combinations of the independent rows, to the tolerance, in ascending order.
+    /// These are the redundant equations of a system with matrix A.
+    pub fn redundant_rows(&self) -> Vec<usize> {
+        let mut rows = self.row_order()[self.rank..].to_vec();
+        rows.sort_unstable();
+        rows
+    }
+
+    ///Returns the columns of A that are independent of each other, in pivot order.
+    pub fn independent_columns(&self) -> Vec<usize> {
+        self.column_order()[..self.rank].to_vec()
+    }
+
+    ///Returns the m x rank unit lower trapezoidal factor L.
+    pub fn lower(&self) -> Matrix<T> {
+        let mut lower = Matrix::new(self.packed.rows(), self.rank);
+        for row in 0..self.packed.rows() {
+            for column in 0..self.rank.min(row + 1) {
+                lower.values[row][column] = if row == column {
+                    T::one()
+                } else {
+                    self.packed.values[row][column].clone()
+                };
+            }
+        }
+        lower
+    }
+
+    ///Returns the rank x n upper trapezoidal factor U.
+    pub fn upper(&self) -> Matrix<T> {
+        let mut upper = Matrix::new(self.rank, self.packed.columns());
+        for row in 0..self.rank {
+            for column in row..self.packed.columns() {
+                upper.values[row][column] = self.packed.values[row][column].clone();
+            }
+        }
+        upper
+    }
+}
+
+///Applies the swaps of each step, in order, to the indices 0..count.
+fn order(count: usize, swaps: &[usize]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..count).collect();
+    for (step, swap_with) in swaps.iter().enumerate() {
+        order.swap(step, *swap_with);
+    }
+    order
+}
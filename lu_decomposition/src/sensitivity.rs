@@ -0,0 +1,130 @@
+use crate::LuDecomposition;
+use matrix::{Matrix, Scalar};
+
+///The gradients of a scalar function of the solution X of A * X = B, with respect to A and B.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Adjoint<T = f32> {
+    ///The solution Λ of A^T * Λ = ∂L/∂X, the gradient with respect to B.
+    pub b: Matrix<T>,
+    ///-Λ * X^T, the gradient with respect to A.
+    pub a: Matrix<T>,
+}
+
+impl<T: Scalar> LuDecomposition<T> {
+    ///Returns the solution Y to A^T * Y = B, from the factors of A. Panics if B has the wrong number of rows.
+    ///
+    /// A = P^T * L * U * Q^T, so A^T = Q * U^T * L^T * P. Applies Q^T, solves with U^T then L^T, and applies P^T.
+    pub fn solve_transposed(&self, b: &Matrix<T>) -> Matrix<T> {
+        let n = self.size();
+        assert_eq!(
+            b.rows(),
+            n,
+            "Right hand side has {} rows. Expected {}.",
+            b.rows(),
+            n
+        );
+        let mut y = b.clone();
+        //Q^T, the column swaps in the order they were made.
+        for (row, swap_with) in self.column_permutation().iter().enumerate() {
+            y.swap_rows((row, *swap_with));
+        }
+        solve_transposed_packed(self.packed(), self.permutation(), &mut y);
+        y
+    }
+
+    ///Returns the derivative of the solution X of A * X = B along a direction, given the derivatives of A and B.
+    ///
+    ///### Usage
+    /// Differentiating A * X = B gives A * dX = dB - dA * X, so each direction costs one solve with the factors of A,
+    /// rather than an elimination over dual numbers.
+    /// ```
+    ///    use lu_decomposition::LuDecomposition;
+    ///    let a = matrix::Matrix::from(vec![vec![2.0f64, 1.0], vec![1.0, 3.0]]);
+    ///    let b = matrix::Matrix::from(vec![vec![3.0], vec![5.0]]);
+    ///    let lu = LuDecomposition::new(a).unwrap();
+    ///    let x = lu.solve(&b);
+    ///    //The derivative with respect to a[0][0].
+    ///    let da = matrix::Matrix::from(vec![vec![1.0, 0.0], vec![0.0, 0.0]]);
+    ///    let dx = lu.tangent(&x, &da, &matrix::Matrix::new(2, 1));
+    ///    assert!((dx.values[0][0] + 0.6 * x.values[0][0]).abs() < 1e-12);
+    /// ```
+    pub fn tangent(&self, x: &Matrix<T>, da: &Matrix<T>, db: &Matrix<T>) -> Matrix<T> {
+        let mut rhs = db.clone();
+        for row in 0..self.size() {
+            for column in 0..x.columns() {
+                let mut sum = rhs.values[row][column].clone();
+                for k in 0..self.size() {
+                    sum -= da.values[row][k].clone() * x.values[k][column].clone();
+                }
+                rhs.values[row][column] = sum;
+            }
+        }
+        self.solve(&rhs)
+    }
+
+    ///Returns the gradients of a scalar L with respect to A and B, given the solution X and the gradient ∂L/∂X.
+    ///
+    ///### Usage
+    /// Reverse mode: one solve with A^T, from the same factors, gives the gradient with respect to every element
+    /// of A and B at once. Prefer it to tangent when there are more parameters than outputs.
+    /// ```
+    ///    use lu_decomposition::LuDecomposition;
+    ///    let a = matrix::Matrix::from(vec![vec![2.0f64, 1.0], vec![1.0, 3.0]]);
+    ///    let b = matrix::Matrix::from(vec![vec![3.0], vec![5.0]]);
+    ///    let lu = LuDecomposition::new(a).unwrap();
+    ///    let x = lu.solve(&b);
+    ///    //L = x[0] + x[1]
+    ///    let gradient = lu.adjoint(&x, &matrix::Matrix::from(vec![vec![1.0], vec![1.0]]));
+    ///    assert!((gradient.b.values[0][0] - 0.4).abs() < 1e-12);
+    ///    assert!((gradient.a.values[0][0] + 0.4 * x.values[0][0]).abs() < 1e-12);
+    /// ```
+    pub fn adjoint(&self, x: &Matrix<T>, gradient: &Matrix<T>) -> Adjoint<T> {
+        let lambda = self.solve_transposed(gradient);
+        let n = self.size();
+        let mut a = Matrix::new(n, n);
+        for row in 0..n {
+            for column in 0..n {
+                let mut sum = T::zero();
+                for k in 0..x.columns() {
+                    sum -= lambda.values[row][k].clone() * x.values[column][k].clone();
+                }
+                a.values[row][column] = sum;
+            }
+        }
+        Adjoint { b: lambda, a }
+    }
+}
+
+///Solves A^T * Y = B in place of `b`, from the packed factors and row permutation of P * A = L * U.
+/// Shared by LuDecomposition::solve_transposed and the condition estimator.
+///
+/// A^T = U^T * L^T * P. Solves with U^T, then L^T, and undoes the row swaps in reverse order.
+pub(crate) fn solve_transposed_packed<T: Scalar>(
+    packed: &Matrix<T>,
+    permutation: &[usize],
+    b: &mut Matrix<T>,
+) {
+    let n = packed.rows();
+    '_traverse_columns: for column in 0..b.columns_unchecked() {
+        //U^T is lower triangular: forward substitution.
+        for row in 0..n {
+            let mut sum = b.values[row][column].clone();
+            for k in 0..row {
+                sum -= packed.values[k][row].clone() * b.values[k][column].clone();
+            }
+            b.values[row][column] = sum / packed.values[row][row].clone();
+        }
+        //L^T is unit upper triangular: back substitution.
+        for row in (0..n).rev() {
+            let mut sum = b.values[row][column].clone();
+            for k in row + 1..n {
+                sum -= packed.values[k][row].clone() * b.values[k][column].clone();
+            }
+            b.values[row][column] = sum;
+        }
+    }
+    //P^T, the row swaps in reverse.
+    for (row, swap_with) in permutation.iter().enumerate().rev() {
+        b.swap_rows((row, *swap_with));
+    }
+}
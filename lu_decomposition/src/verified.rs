@@ -0,0 +1,172 @@
+use crate::{DecomposeOptions, Error, LuDecomposition, SingularPivot};
+use matrix::interval::Interval;
+use matrix::{Field, Matrix};
+
+///The choices solve_verified may make.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VerifiedSolveOptions {
+    ///The most Krawczyk steps taken before verification is reported to have failed.
+    pub max_iterations: usize,
+    ///How far each candidate enclosure is widened before the next step, relative to its width. Rump takes 0.1.
+    pub inflation: f64,
+    ///How A is decomposed for the approximate solution and inverse. A zero pivot always stops the solve.
+    pub decompose: DecomposeOptions,
+}
+
+impl Default for VerifiedSolveOptions {
+    fn default() -> Self {
+        VerifiedSolveOptions {
+            max_iterations: 10,
+            inflation: 0.1,
+            decompose: DecomposeOptions::default(),
+        }
+    }
+}
+
+///The solution of solve_verified.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VerifiedSolution {
+    ///Intervals proven to contain the exact solution of A * X = B, one for each element of X.
+    pub enclosure: Matrix<Interval>,
+    ///The floating point solution the enclosure was built around.
+    pub approximation: Matrix<f64>,
+    ///The Krawczyk steps taken before the enclosure was proven.
+    pub iterations: usize,
+}
+
+///Solves A * X = B, and proves bounds on the exact solution, by Krawczyk's method with Rump's epsilon inflation.
+///
+///### Usage
+/// Decomposes A with decompose and solves with crout for an approximate solution X~ and an approximate inverse R.
+/// The error E = X - X~ of the exact solution X then satisfies E = R * (B - A * X~) + (I - R * A) * E.
+/// In interval arithmetic with outward rounding, a candidate enclosure Y of E is widened, and mapped through
+/// the right hand side. If the image lies in the interior of Y, Brouwer's fixed point theorem proves that A is
+/// nonsingular and that X lies in X~ + the image. The proof holds for the exact A and B, rounding errors included.
+/// ```
+///    use lu_decomposition::solve_verified;
+///    let a = matrix::Matrix::from(vec![vec![4.0, 1.0], vec![1.0, 3.0]]);
+///    let b = matrix::Matrix::from(vec![vec![1.0], vec![2.0]]);
+///    let result = solve_verified(&a, &b).unwrap();
+///    let x = result.enclosure.values[0][0];
+///    assert!(x.contains(1.0 / 11.0));
+///    assert!(x.width() < 1e-15);
+/// ```
+///### Errors
+/// `NotSquare` for a rectangular A. `ZeroPivot` if A is singular in f64. `NotVerified` if no enclosure was proven,
+/// which happens when cond(A) nears 1 / f64 epsilon, about 10^16.
+pub fn solve_verified(a: &Matrix<f64>, b: &Matrix<f64>) -> Result<VerifiedSolution, Error> {
+    solve_verified_with(a, b, VerifiedSolveOptions::default())
+}
+
+///Performs solve_verified with the given options.
+pub fn solve_verified_with(
+    a: &Matrix<f64>,
+    b: &Matrix<f64>,
+    options: VerifiedSolveOptions,
+) -> Result<VerifiedSolution, Error> {
+    let (rows, columns) = (a.rows(), a.columns());
+    if rows != columns {
+        return Err(Error::NotSquare { rows, columns });
+    }
+    let decompose = DecomposeOptions {
+        singular_pivot: SingularPivot::Fail,
+        ..options.decompose
+    };
+    let lu = LuDecomposition::with_options(a.clone(), decompose)?;
+    let (approximation, inverse) = (lu.solve(b), lu.inverse());
+    let finite = |matrix: &Matrix<f64>| matrix.values().iter().flatten().all(|v| v.is_finite());
+    if !finite(b) || !finite(&approximation) || !finite(&inverse) {
+        return Err(Error::NotVerified { iterations: 0 });
+    }
+    let inverse = intervals(&inverse);
+    let (a_intervals, x_intervals) = (intervals(a), intervals(&approximation));
+
+    //Z = R * (B - A * X~), an enclosure of R times the residual.
+    let mut residual = intervals(b);
+    let product = multiply(&a_intervals, &x_intervals);
+    for (row, values) in residual.values.iter_mut().enumerate() {
+        for (column, value) in values.iter_mut().enumerate() {
+            *value -= product.values[row][column];
+        }
+    }
+    let z = multiply(&inverse, &residual);
+    //C = I - R * A, which is small when R is near the inverse of A.
+    let mut c = multiply(&inverse, &a_intervals);
+    for (row, values) in c.values.iter_mut().enumerate() {
+        for (column, value) in values.iter_mut().enumerate() {
+            let identity = if row == column { 1.0 } else { 0.0 };
+            *value = Interval::point(identity) - *value;
+        }
+    }
+
+    let mut y = z.clone();
+    '_krawczyk: for iteration in 1..=options.max_iterations {
+        let candidate = inflate(&y, options.inflation);
+        y = multiply(&c, &candidate);
+        for (row, values) in y.values.iter_mut().enumerate() {
+            for (column, value) in values.iter_mut().enumerate() {
+                *value += z.values[row][column];
+            }
+        }
+        let contracted = candidate
+            .values
+            .iter()
+            .flatten()
+            .zip(y.values.iter().flatten())
+            .all(|(candidate, image)| candidate.contains_in_interior(image));
+        if contracted {
+            let mut enclosure = y;
+            for (row, values) in enclosure.values.iter_mut().enumerate() {
+                for (column, value) in values.iter_mut().enumerate() {
+                    *value += x_intervals.values[row][column];
+                }
+            }
+            return Ok(VerifiedSolution {
+                enclosure,
+                approximation,
+                iterations: iteration,
+            });
+        }
+    }
+    Err(Error::NotVerified {
+        iterations: options.max_iterations,
+    })
+}
+
+///Widens each interval by `inflation` times its width on either side, and by the smallest normal f64,
+/// so that an interval of zero width grows too.
+fn inflate(y: &Matrix<Interval>, inflation: f64) -> Matrix<Interval> {
+    let tiny = Interval::new(-f64::MIN_POSITIVE, f64::MIN_POSITIVE);
+    let mut inflated = y.clone();
+    for value in inflated.values.iter_mut().flatten() {
+        let spread = Interval::new(-1.0, 1.0) * Interval::point(inflation * value.width());
+        *value = *value + spread + tiny;
+    }
+    inflated
+}
+
+///The point intervals of the elements.
+fn intervals(matrix: &Matrix<f64>) -> Matrix<Interval> {
+    let mut intervals = Matrix::new(matrix.rows(), matrix.columns());
+    for (row, values) in matrix.values().iter().enumerate() {
+        for (column, value) in values.iter().enumerate() {
+            intervals.values[row][column] = Interval::point(*value);
+        }
+    }
+    intervals
+}
+
+///The product of interval matrices, each element enclosing the exact sum of products.
+fn multiply(a: &Matrix<Interval>, b: &Matrix<Interval>) -> Matrix<Interval> {
+    let mut product = Matrix::new(a.rows(), b.columns());
+    for row in 0..a.rows() {
+        for column in 0..b.columns() {
+            let mut sum = Interval::zero();
+            for k in 0..a.columns() {
+                sum += a.values[row][k] * b.values[k][column];
+            }
+            product.values[row][column] = sum;
+        }
+    }
+    product
+}
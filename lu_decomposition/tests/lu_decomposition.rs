@@ -60,8 +60,8 @@ fn return_solution_in_place_of_3x1() {
     let result = decompose(&mut matrix);
     let (permutation, ..) = result.unwrap();
     let mut b = matrix::Matrix::from(vec![vec![19.5], vec![6.0], vec![48.0]]);
-    let solution = matrix::Matrix::from(vec![vec![1.0], vec![0.0], vec![0.25]]);
-    crout(&matrix, &permutation, &mut b); //Rounding error present. Why?
+    let solution = matrix::Matrix::from(vec![vec![0.0], vec![0.25], vec![1.0]]);
+    crout(&matrix, &permutation, &mut b);
     assert_eq!(b, solution);
 }
 #[test]
@@ -93,3 +93,1052 @@ fn return_determinant() {
     }
     assert_eq!(determinant, solution);
 }
+
+#[test]
+fn lu_decomposition_solve() {
+    use lu_decomposition::LuDecomposition;
+    let a = matrix::Matrix::from(vec![
+        vec![-12.0, 6.0, 18.0],
+        vec![6.0, 4.0, 5.0],
+        vec![-48.0, 24.0, 42.0],
+    ]);
+    let lu = LuDecomposition::new(a).unwrap();
+    let b = matrix::Matrix::from(vec![vec![19.5], vec![6.0], vec![48.0]]);
+    let solution = matrix::Matrix::from(vec![vec![0.0], vec![0.25], vec![1.0]]);
+    assert_eq!(lu.solve(&b), solution);
+
+    let mut b = b;
+    lu.solve_in_place(&mut b);
+    assert_eq!(b, solution);
+}
+
+#[test]
+fn return_solution_in_place_of_3x2() {
+    use lu_decomposition::{crout, decompose};
+    let mut matrix = matrix::Matrix::from(vec![
+        vec![-12.0, 6.0, 18.0],
+        vec![6.0, 4.0, 5.0],
+        vec![-48.0, 24.0, 42.0],
+    ]);
+    let (permutation, ..) = decompose(&mut matrix).unwrap();
+    //Each column is solved independently of its neighbours.
+    let mut b = matrix::Matrix::from(vec![
+        vec![19.5, 19.5, 0.0],
+        vec![6.0, 6.0, 0.0],
+        vec![48.0, 48.0, 0.0],
+    ]);
+    let solution = matrix::Matrix::from(vec![
+        vec![0.0, 0.0, 0.0],
+        vec![0.25, 0.25, 0.0],
+        vec![1.0, 1.0, 0.0],
+    ]);
+    crout(&matrix, &permutation, &mut b);
+    assert_eq!(b, solution);
+}
+
+#[test]
+fn lu_decomposition_inverse_and_determinant() {
+    use lu_decomposition::LuDecomposition;
+    let a = matrix::Matrix::from(vec![vec![3.0, -2.0], vec![6.0, 4.0]]);
+    let lu = LuDecomposition::new(a).unwrap();
+    let inverse = matrix::Matrix::from(vec![
+        vec![4.0 / 24.0, 2.0 / 24.0],
+        vec![-6.0 / 24.0, 3.0 / 24.0],
+    ]);
+    assert_eq!(lu.inverse(), inverse);
+    assert_eq!(lu.determinant(), 24.0);
+
+    let (sign, log_abs_determinant) = lu.log_abs_determinant();
+    assert_eq!(sign, 1.0);
+    assert!((log_abs_determinant - 24.0f64.ln()).abs() < 1e-6);
+}
+
+#[test]
+fn lu_decomposition_log_abs_determinant_does_not_overflow() {
+    use lu_decomposition::LuDecomposition;
+    let mut a: matrix::Matrix<f32> = matrix::Matrix::identity(40);
+    for row in 0..a.rows() {
+        a.values[row][row] = -1.0e3;
+    }
+    let lu = LuDecomposition::new(a).unwrap();
+    assert!(lu.determinant().is_infinite());
+
+    let (sign, log_abs_determinant) = lu.log_abs_determinant();
+    assert_eq!(sign, 1.0);
+    assert!((log_abs_determinant - 40.0 * 1.0e3f64.ln()).abs() < 1e-3);
+}
+
+#[test]
+fn lu_decomposition_factors() {
+    use lu_decomposition::LuDecomposition;
+    let a = matrix::Matrix::from(vec![
+        vec![-12.0, 6.0, 18.0],
+        vec![6.0, 4.0, 5.0],
+        vec![-48.0, 24.0, 42.0],
+    ]);
+    let lu = LuDecomposition::new(a).unwrap();
+    let lower = matrix::Matrix::from(vec![
+        vec![1.0, 0.0, 0.0],
+        vec![-8.0, 1.0, 0.0],
+        vec![-2.0, 0.25, 1.0],
+    ]);
+    let upper = matrix::Matrix::from(vec![
+        vec![6.0, 4.0, 5.0],
+        vec![0.0, 56.0, 82.0],
+        vec![0.0, 0.0, 7.5],
+    ]);
+    //Row 0 swapped with row 1, then row 1 swapped with row 2.
+    let permutation = matrix::Matrix::from(vec![
+        vec![0.0, 1.0, 0.0],
+        vec![0.0, 0.0, 1.0],
+        vec![1.0, 0.0, 0.0],
+    ]);
+    assert_eq!(lu.lower(), lower);
+    assert_eq!(lu.upper(), upper);
+    assert_eq!(lu.permutation_matrix(), permutation);
+    assert_eq!(lu.parity(), 1);
+}
+
+#[test]
+fn reciprocal_condition_bounds_explicit_inverse() {
+    use lu_decomposition::{decompose, reciprocal_condition, LuDecomposition};
+    let a = matrix::Matrix::from(vec![
+        vec![-12.0, 6.0, 18.0],
+        vec![6.0, 4.0, 5.0],
+        vec![-48.0, 24.0, 42.0],
+    ]);
+    let exact = 1.0
+        / (a.norm_one()
+            * LuDecomposition::new(a.clone())
+                .unwrap()
+                .inverse()
+                .norm_one());
+
+    let mut packed = a.clone();
+    let (permutation, ..) = decompose(&mut packed).unwrap();
+    let estimate = reciprocal_condition(&packed, &permutation, a.norm_one());
+    //||A^-1|| is estimated from below, so the reciprocal is estimated from above, usually within a factor of 3.
+    assert!(estimate >= exact * (1.0 - 1e-6));
+    assert!(estimate <= 3.0 * exact);
+}
+
+#[test]
+fn condition_warning_is_opt_in() {
+    use lu_decomposition::LuDecomposition;
+    let ill_conditioned = matrix::Matrix::from(vec![vec![1.0, 1.0], vec![1.0, 1.0001]]);
+    let lu = LuDecomposition::new(ill_conditioned).unwrap();
+    assert!(lu.reciprocal_condition() < 1.0e-3);
+    assert_eq!(lu.condition_warning(), None);
+
+    let warning = lu
+        .with_condition_warning(1.0e-3)
+        .condition_warning()
+        .unwrap();
+    assert_eq!(warning.threshold, 1.0e-3);
+    assert!(warning.reciprocal_condition < 1.0e-3);
+
+    let well_conditioned = matrix::Matrix::from(vec![vec![3.0, -2.0], vec![6.0, 4.0]]);
+    let lu = LuDecomposition::new(well_conditioned)
+        .unwrap()
+        .with_condition_warning(1.0e-3);
+    assert_eq!(lu.condition_warning(), None);
+}
+
+#[test]
+fn trace_records_decompose_and_crout() {
+    use lu_decomposition::{crout_with, decompose_with, CroutOptions, DecomposeOptions};
+    use matrix::trace::{Recorder, Step};
+    let mut matrix = matrix::Matrix::from(vec![vec![-2.0, 3.0], vec![6.0, 4.0]]);
+    let mut recorder = Recorder::new();
+    let options = DecomposeOptions::default();
+    let (pivots, ..) = decompose_with(&mut matrix, options, &mut recorder).unwrap();
+    assert_eq!(
+        recorder.steps(),
+        vec![
+            Step::SelectPivot { row: 1, column: 0 },
+            Step::SwapRows { rows: (1, 0) },
+            Step::ScaleColumn {
+                column: 0,
+                scale: 1.0 / 6.0
+            },
+            Step::Substitute { row: 1, column: 1 },
+            Step::SelectPivot { row: 1, column: 1 },
+        ]
+    );
+    assert_eq!(recorder.frames().last().unwrap().after.a, matrix);
+
+    let mut b = matrix::Matrix::from(vec![vec![4.0], vec![-8.0]]);
+    let mut recorder = Recorder::new();
+    crout_with(
+        &matrix,
+        &pivots.rows,
+        &mut b,
+        CroutOptions::default(),
+        &mut recorder,
+    );
+    let steps = recorder.steps();
+    assert_eq!(
+        steps[0],
+        Step::SwapElements {
+            rows: (0, 1),
+            column: 0
+        }
+    );
+    assert_eq!(recorder.frames().last().unwrap().after.b, Some(b));
+}
+
+#[test]
+fn implicit_scaling_changes_the_pivot() {
+    use lu_decomposition::{decompose_with, DecomposeOptions};
+    use matrix::pivoting::Pivoting;
+    use matrix::trace::NoTrace;
+    //Row 0 is small in absolute terms, but large relative to the rest of its row.
+    let original = matrix::Matrix::from(vec![vec![2.0, 1.0], vec![10.0, 100.0]]);
+
+    let mut scaled = original.clone();
+    let (pivots, ..) =
+        decompose_with(&mut scaled, DecomposeOptions::default(), &mut NoTrace).unwrap();
+    assert_eq!(pivots.rows, vec![0, 1]);
+
+    let mut unscaled = original;
+    let options = DecomposeOptions {
+        pivoting: Pivoting::Partial,
+        ..DecomposeOptions::default()
+    };
+    let (pivots, ..) = decompose_with(&mut unscaled, options, &mut NoTrace).unwrap();
+    assert_eq!(pivots.rows, vec![1, 1]);
+}
+
+#[test]
+fn skip_leading_zeros_does_not_change_the_solution() {
+    use lu_decomposition::{crout_with, decompose, CroutOptions};
+    use matrix::trace::Recorder;
+    let mut matrix = matrix::Matrix::from(vec![
+        vec![-12.0, 6.0, 18.0],
+        vec![6.0, 4.0, 5.0],
+        vec![-48.0, 24.0, 42.0],
+    ]);
+    let (permutation, ..) = decompose(&mut matrix).unwrap();
+
+    let mut skipped = matrix::Matrix::identity(3);
+    let mut skipped_steps = Recorder::new();
+    let options = CroutOptions::default();
+    crout_with(
+        &matrix,
+        &permutation,
+        &mut skipped,
+        options,
+        &mut skipped_steps,
+    );
+
+    let mut full = matrix::Matrix::identity(3);
+    let mut full_steps = Recorder::new();
+    let options = CroutOptions {
+        skip_leading_zeros: false,
+        ..CroutOptions::default()
+    };
+    crout_with(&matrix, &permutation, &mut full, options, &mut full_steps);
+
+    assert_eq!(skipped, full);
+    assert!(skipped_steps.frames().len() < full_steps.frames().len());
+}
+
+#[test]
+fn skip_leading_zeros_saves_operations() {
+    use lu_decomposition::{crout_with, decompose, CroutOptions};
+    use matrix::count::{count_operations, Counted};
+    use matrix::trace::NoTrace;
+    let n = 10;
+    let mut matrix = matrix::Matrix::new(n, n);
+    for row in 0..n {
+        for column in 0..n {
+            matrix.values[row][column] = Counted(1.0 / (row + column + 1) as f64);
+        }
+        matrix.values[row][row] += Counted(1.0);
+    }
+    let (permutation, _) = decompose(&mut matrix).unwrap();
+
+    let solve = |skip_leading_zeros: bool| {
+        count_operations(|| {
+            let mut identity = matrix::Matrix::identity(n);
+            let options = CroutOptions {
+                skip_leading_zeros,
+                ..CroutOptions::default()
+            };
+            crout_with(&matrix, &permutation, &mut identity, options, &mut NoTrace);
+            identity
+        })
+    };
+    let (skipped, skipped_counts) = solve(true);
+    let (full, full_counts) = solve(false);
+
+    assert_eq!(skipped, full);
+    assert!(skipped_counts.multiplications < full_counts.multiplications);
+    assert!(skipped_counts.additions < full_counts.additions);
+}
+
+#[test]
+fn verify_bounds_the_error_of_a_hilbert_solve() {
+    use lu_decomposition::LuDecomposition;
+    use matrix::verify::verify;
+    let n = 6;
+    let mut a = matrix::Matrix::new(n, n);
+    for row in 0..n {
+        for column in 0..n {
+            a.values[row][column] = 1.0 / (row + column + 1) as f64;
+        }
+    }
+    //b = A * [1, 1, ..., 1]
+    let exact = matrix::Matrix::from(vec![vec![1.0]; n]);
+    let mut b = matrix::Matrix::new(n, 1);
+    for row in 0..n {
+        b.values[row][0] = a.values[row].iter().sum();
+    }
+
+    let lu = LuDecomposition::new(a.clone()).unwrap();
+    let x = lu.solve(&b);
+    let verification = verify(&a, &x, &b, Some(lu.reciprocal_condition()));
+    assert!(verification.is_acceptable(1e-14));
+
+    let mut forward_error = 0.0;
+    for row in 0..n {
+        forward_error += (x.values[row][0] - exact.values[row][0]).abs();
+    }
+    forward_error /= n as f64;
+    assert!(forward_error > 0.0);
+    assert!(forward_error <= verification.forward_error_bound.unwrap());
+}
+
+#[test]
+fn every_pivoting_strategy_factors_and_solves() {
+    use lu_decomposition::{DecomposeOptions, LuDecomposition};
+    use matrix::pivoting::Pivoting;
+    let a: matrix::Matrix<f64> = matrix::Matrix::from(vec![
+        vec![1.0, 5.0, 0.0],
+        vec![2.0, 3.0, 9.0],
+        vec![4.0, 6.0, 1.0],
+    ]);
+    //b = A * [1, 2, 3]
+    let b = matrix::Matrix::from(vec![vec![11.0], vec![35.0], vec![19.0]]);
+    for pivoting in [
+        Pivoting::None,
+        Pivoting::Partial,
+        Pivoting::ScaledPartial,
+        Pivoting::Complete,
+        Pivoting::Rook,
+    ] {
+        let lu = LuDecomposition::with_options(
+            a.clone(),
+            DecomposeOptions {
+                pivoting,
+                ..DecomposeOptions::default()
+            },
+        )
+        .unwrap();
+        let x = lu.solve(&b);
+        for (row, expected) in [1.0, 2.0, 3.0].iter().enumerate() {
+            assert!(
+                (x.values[row][0] - expected).abs() < 1e-12,
+                "{:?}",
+                pivoting
+            );
+        }
+        assert!((lu.determinant() - 119.0).abs() < 1e-12, "{:?}", pivoting);
+
+        //P * A * Q = L * U
+        let p_a_q = multiply(
+            &multiply(&lu.permutation_matrix(), &a),
+            &lu.column_permutation_matrix(),
+        );
+        let l_u = multiply(&lu.lower(), &lu.upper());
+        for row in 0..3 {
+            for column in 0..3 {
+                let error = p_a_q.values[row][column] - l_u.values[row][column];
+                assert!(error.abs() < 1e-12, "{:?}", pivoting);
+            }
+        }
+    }
+}
+
+#[test]
+fn complete_and_rook_pivoting_swap_columns() {
+    use lu_decomposition::{decompose_with, DecomposeOptions};
+    use matrix::pivoting::Pivoting;
+    use matrix::trace::NoTrace;
+    let a = matrix::Matrix::from(vec![
+        vec![1.0, 5.0, 0.0],
+        vec![2.0, 3.0, 9.0],
+        vec![4.0, 6.0, 1.0],
+    ]);
+    let decompose = |pivoting| {
+        let mut a = a.clone();
+        decompose_with(
+            &mut a,
+            DecomposeOptions {
+                pivoting,
+                ..DecomposeOptions::default()
+            },
+            &mut NoTrace,
+        )
+        .unwrap()
+        .0
+    };
+    let partial = decompose(Pivoting::Partial);
+    assert_eq!(
+        (partial.rows, partial.columns),
+        (vec![2, 2, 2], vec![0, 1, 2])
+    );
+    let complete = decompose(Pivoting::Complete);
+    assert_eq!(complete.columns[0], 2);
+    assert_eq!(complete.rows[0], 1);
+    let rook = decompose(Pivoting::Rook);
+    assert_eq!((rook.rows[0], rook.columns[0]), (2, 1));
+}
+
+#[test]
+fn rectangular_matrices_factor_with_every_strategy() {
+    use lu_decomposition::{decompose_with, DecomposeOptions};
+    use matrix::pivoting::Pivoting;
+    use matrix::trace::NoTrace;
+    let tall = matrix::Matrix::from(vec![
+        vec![1.0, 2.0],
+        vec![3.0, 4.0],
+        vec![5.0, 7.0],
+        vec![0.0, 1.0],
+    ]);
+    let wide = matrix::Matrix::from(vec![vec![2.0, 1.0, 0.0, 3.0], vec![4.0, 1.0, 5.0, 1.0]]);
+    for a in [tall, wide] {
+        for pivoting in [
+            Pivoting::None,
+            Pivoting::Partial,
+            Pivoting::ScaledPartial,
+            Pivoting::Complete,
+            Pivoting::Rook,
+        ] {
+            let mut packed = a.clone();
+            let (pivots, ..) = decompose_with(
+                &mut packed,
+                DecomposeOptions {
+                    pivoting,
+                    ..DecomposeOptions::default()
+                },
+                &mut NoTrace,
+            )
+            .unwrap();
+            assert_eq!(pivots.rows.len(), a.rows().min(a.columns()));
+            let (lower, upper) = trapezoids(&packed, pivots.rows.len());
+            let product = multiply(&lower, &upper);
+            let expected = permuted(&a, &pivots);
+            for row in 0..a.rows() {
+                for column in 0..a.columns() {
+                    let error = product.values[row][column] - expected.values[row][column];
+                    assert!(error.abs() < 1e-12, "{:?}", pivoting);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn rectangular_matrix_with_a_zero_column() {
+    use lu_decomposition::{decompose_with, DecomposeOptions, LuDecomposition};
+    use matrix::trace::NoTrace;
+    let a = matrix::Matrix::from(vec![vec![0.0, 1.0, 2.0], vec![0.0, 3.0, 4.0]]);
+    let mut packed = a.clone();
+    let options = DecomposeOptions::default();
+    let (pivots, ..) = decompose_with(&mut packed, options, &mut NoTrace).unwrap();
+    let (lower, upper) = trapezoids(&packed, 2);
+    assert_eq!(multiply(&lower, &upper), permuted(&a, &pivots));
+    assert_eq!(
+        LuDecomposition::new(a).unwrap_err(),
+        lu_decomposition::Error::NotSquare {
+            rows: 2,
+            columns: 3
+        }
+    );
+}
+
+#[test]
+fn rank_revealing_finds_redundant_equations() {
+    use lu_decomposition::RankRevealingLu;
+    //The third equation is the sum of the first two.
+    let a: matrix::Matrix<f64> = matrix::Matrix::from(vec![
+        vec![1.0, 2.0, 1.0],
+        vec![0.0, 1.0, 3.0],
+        vec![1.0, 3.0, 4.0],
+        vec![2.0, 1.0, 0.0],
+    ]);
+    let lu = RankRevealingLu::new(a.clone(), 1.0e-12);
+    assert_eq!(lu.rank(), 3);
+    assert_eq!(lu.redundant_rows().len(), 1);
+    assert!(lu.redundant_rows()[0] <= 2);
+    let mut independent = lu.independent_rows();
+    independent.extend(lu.redundant_rows());
+    independent.sort_unstable();
+    assert_eq!(independent, vec![0, 1, 2, 3]);
+    assert_eq!(lu.independent_columns().len(), 3);
+
+    //L * U reproduces the independent rows in pivot order.
+    let product = multiply(&lu.lower(), &lu.upper());
+    let expected = permuted(&a, lu.pivots());
+    for row in 0..lu.rank() {
+        for column in 0..a.columns() {
+            let error = product.values[row][column] - expected.values[row][column];
+            assert!(error.abs() < 1e-12);
+        }
+    }
+
+    //A perturbation below the tolerance is still redundant. Without a tolerance it is not.
+    let mut perturbed = matrix::Matrix::from(a.values[..3].to_vec());
+    perturbed.values[2][0] += 1.0e-10;
+    assert_eq!(RankRevealingLu::new(perturbed.clone(), 1.0e-8).rank(), 2);
+    assert_eq!(RankRevealingLu::new(perturbed, 0.0).rank(), 3);
+    let zero: matrix::Matrix<f64> = matrix::Matrix::new(2, 3);
+    assert_eq!(RankRevealingLu::new(zero, 0.0).rank(), 0);
+}
+
+#[test]
+fn rank_revealing_reports_rank_against_the_tolerance() {
+    use lu_decomposition::RankRevealingLu;
+    let a: matrix::Matrix<f64> = matrix::Matrix::from(vec![
+        vec![1.0, 1.0, 1.0],
+        vec![1.0, 1.0 + 1.0e-9, 1.0],
+        vec![1.0, 1.0, 1.0],
+    ]);
+    assert_eq!(RankRevealingLu::new(a.clone(), 1.0e-6).rank(), 1);
+    assert_eq!(RankRevealingLu::new(a.clone(), 1.0e-12).rank(), 2);
+    assert_eq!(RankRevealingLu::new(a, 1.0e-6).redundant_rows(), vec![0, 2]);
+}
+
+#[test]
+fn singular_pivot_policies() {
+    use lu_decomposition::{
+        decompose_with, DecomposeOptions, Error, LuDecomposition, SingularPivot,
+    };
+    use matrix::pivoting::Pivoting;
+    use matrix::trace::NoTrace;
+    //The second pivot is exactly zero, whichever way the pivots are chosen.
+    let a: matrix::Matrix<f64> = matrix::Matrix::from(vec![vec![2.0, 1.0], vec![4.0, 2.0]]);
+    let decompose = |pivoting, singular_pivot| {
+        let mut packed = a.clone();
+        let options = DecomposeOptions {
+            pivoting,
+            singular_pivot,
+            ..DecomposeOptions::default()
+        };
+        decompose_with(&mut packed, options, &mut NoTrace)
+            .map(|(_, _, substitutions)| (packed.values[1][1], substitutions))
+    };
+    for pivoting in [Pivoting::ScaledPartial, Pivoting::Complete] {
+        assert_eq!(
+            decompose(pivoting, SingularPivot::Fail).unwrap_err(),
+            Error::ZeroPivot { column: 1 }
+        );
+        assert_eq!(
+            decompose(pivoting, SingularPivot::Tiny(None)).unwrap(),
+            (f64::EPSILON * f64::EPSILON, vec![1])
+        );
+        assert_eq!(
+            decompose(pivoting, SingularPivot::Tiny(Some(1.0e-20))).unwrap(),
+            (1.0e-20, vec![1])
+        );
+        assert_eq!(
+            decompose(pivoting, SingularPivot::Perturb(None)).unwrap(),
+            (6.0 * f64::EPSILON, vec![1])
+        );
+        assert_eq!(
+            decompose(pivoting, SingularPivot::Perturb(Some(1.0e-3))).unwrap(),
+            (6.0e-3, vec![1])
+        );
+    }
+
+    //The default TINY is tiny for f32 as well, and the substitution is reported.
+    let single: matrix::Matrix<f32> = matrix::Matrix::from(vec![vec![2.0, 1.0], vec![4.0, 2.0]]);
+    let lu = LuDecomposition::new(single).unwrap();
+    assert_eq!(lu.substitutions(), &[1]);
+    assert_eq!(lu.packed().values[1][1], f32::EPSILON * f32::EPSILON);
+    let regular: matrix::Matrix<f32> = matrix::Matrix::from(vec![vec![2.0, 1.0], vec![4.0, 3.0]]);
+    assert!(LuDecomposition::new(regular)
+        .unwrap()
+        .substitutions()
+        .is_empty());
+}
+
+///Returns the m x k unit lower trapezoid and the k x n upper trapezoid packed in `packed`.
+fn trapezoids(
+    packed: &matrix::Matrix<f64>,
+    k: usize,
+) -> (matrix::Matrix<f64>, matrix::Matrix<f64>) {
+    let mut lower = matrix::Matrix::new(packed.rows(), k);
+    let mut upper = matrix::Matrix::new(k, packed.columns());
+    for row in 0..packed.rows() {
+        for column in 0..packed.columns() {
+            let value = packed.values[row][column];
+            if column < row.min(k) {
+                lower.values[row][column] = value;
+            } else if row < k {
+                upper.values[row][column] = value;
+            }
+        }
+        if row < k {
+            lower.values[row][row] = 1.0;
+        }
+    }
+    (lower, upper)
+}
+
+///Returns P * A * Q, applying the row and column swap of each step in order.
+fn permuted(a: &matrix::Matrix<f64>, pivots: &matrix::pivoting::Pivots) -> matrix::Matrix<f64> {
+    let mut permuted = a.clone();
+    for (step, (row, column)) in pivots.rows.iter().zip(&pivots.columns).enumerate() {
+        permuted.swap_rows((step, *row));
+        permuted.swap_columns((step, *column));
+    }
+    permuted
+}
+
+#[test]
+fn blocked_decomposition_matches_decompose() {
+    use lu_decomposition::{crout, decompose_blocked_with, decompose_with, DecomposeOptions};
+    use matrix::pivoting::Pivoting;
+    use matrix::trace::NoTrace;
+    for (rows, columns) in [(37, 37), (23, 9), (9, 23)] {
+        let a = pseudo_random(rows, columns, 7);
+        for pivoting in [Pivoting::None, Pivoting::Partial, Pivoting::ScaledPartial] {
+            let options = DecomposeOptions {
+                pivoting,
+                ..DecomposeOptions::default()
+            };
+            let mut textbook = a.clone();
+            let expected = decompose_with(&mut textbook, options, &mut NoTrace).unwrap();
+            for block_size in [1, 4, 8, 64] {
+                let mut blocked = a.clone();
+                let result = decompose_blocked_with(&mut blocked, block_size, options).unwrap();
+                assert_eq!(result, expected, "{:?} {}", pivoting, block_size);
+                for row in 0..rows {
+                    for column in 0..columns {
+                        let error = blocked.values[row][column] - textbook.values[row][column];
+                        assert!(error.abs() < 1e-9, "{:?} {}", pivoting, block_size);
+                    }
+                }
+            }
+        }
+    }
+
+    //crout solves with the blocked factors unchanged.
+    let a = pseudo_random(37, 37, 11);
+    let x = pseudo_random(37, 2, 13);
+    let mut b = multiply(&a, &x);
+    let mut packed = a;
+    let (permutation, _) = lu_decomposition::decompose_blocked(&mut packed, 8).unwrap();
+    crout(&packed, &permutation, &mut b);
+    for row in 0..37 {
+        for column in 0..2 {
+            assert!((b.values[row][column] - x.values[row][column]).abs() < 1e-9);
+        }
+    }
+}
+
+#[test]
+fn parallel_crout_gives_the_serial_solution() {
+    use lu_decomposition::{crout_with, decompose, CroutOptions};
+    use matrix::trace::NoTrace;
+    let mut a = pseudo_random(37, 37, 3);
+    let (permutation, _) = decompose(&mut a).unwrap();
+    let mut b = pseudo_random(37, 11, 5);
+    //Leading zeros in some columns, so skip_leading_zeros acts differently on each.
+    for column in 0..11 {
+        for row in 0..column {
+            b.values[row][column] = 0.0;
+        }
+    }
+    let solve = |threads| {
+        let mut x = b.clone();
+        let options = CroutOptions {
+            threads,
+            ..CroutOptions::default()
+        };
+        crout_with(&a, &permutation, &mut x, options, &mut NoTrace);
+        x
+    };
+    let serial = solve(1);
+    for threads in [2, 3, 4, 11, 16] {
+        assert_eq!(solve(threads), serial, "{} threads", threads);
+    }
+}
+
+#[test]
+fn simd_kernels_agree_with_the_plain_loops() {
+    use lu_decomposition::{crout_with, decompose_blocked_with, decompose_with};
+    use lu_decomposition::{CroutOptions, DecomposeOptions};
+    use matrix::pivoting::Pivoting;
+    use matrix::trace::NoTrace;
+    let a = pseudo_random(45, 45, 19);
+    let b = pseudo_random(45, 3, 23);
+    for pivoting in [Pivoting::ScaledPartial, Pivoting::Complete] {
+        let solve = |simd| {
+            let options = DecomposeOptions {
+                pivoting,
+                simd,
+                ..DecomposeOptions::default()
+            };
+            let (mut packed, mut blocked, mut x) = (a.clone(), a.clone(), b.clone());
+            let (pivots, ..) = decompose_with(&mut packed, options, &mut NoTrace).unwrap();
+            decompose_blocked_with(&mut blocked, 8, options).unwrap();
+            let options = CroutOptions {
+                simd,
+                ..CroutOptions::default()
+            };
+            crout_with(&packed, &pivots.rows, &mut x, options, &mut NoTrace);
+            (pivots, packed, blocked, x)
+        };
+        let (plain_pivots, plain, plain_blocked, plain_x) = solve(false);
+        let (pivots, packed, blocked, x) = solve(true);
+        assert_eq!(pivots, plain_pivots);
+        for (vectorized, plain) in [(packed, plain), (blocked, plain_blocked), (x, plain_x)] {
+            for row in 0..vectorized.rows() {
+                for column in 0..vectorized.columns() {
+                    let error = vectorized.values[row][column] - plain.values[row][column];
+                    assert!(error.abs() < 1e-10, "{:?}", pivoting);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn mixed_precision_refines_f32_factors_to_f64_accuracy() {
+    use lu_decomposition::{solve_mixed_precision, LuDecomposition, SolvePath};
+    let n = 40;
+    let mut a = pseudo_random(n, n, 29);
+    for row in 0..n {
+        a.values[row][row] += 10.0;
+    }
+    let b = pseudo_random(n, 2, 31);
+    let mixed = solve_mixed_precision(&a, &b).unwrap();
+    match mixed.path {
+        SolvePath::Refined { iterations } => assert!((1..=4).contains(&iterations)),
+        path => panic!("{:?}", path),
+    }
+    assert!(mixed.backward_error <= (n as f64).sqrt() * f64::EPSILON);
+    let double = LuDecomposition::new(a).unwrap().solve(&b);
+    for row in 0..n {
+        for column in 0..2 {
+            let error = mixed.solution.values[row][column] - double.values[row][column];
+            assert!(error.abs() < 1e-13);
+        }
+    }
+}
+
+#[test]
+fn mixed_precision_falls_back_to_f64() {
+    use lu_decomposition::{solve_mixed_precision, Error, Fallback, SolvePath};
+    let fallback = |a: Vec<Vec<f64>>| {
+        let a = matrix::Matrix::from(a);
+        let b = matrix::Matrix::from(vec![vec![1.0]; a.rows()]);
+        solve_mixed_precision(&a, &b).map(|solution| (solution.path, solution.backward_error))
+    };
+    let reason = |a| match fallback(a).unwrap() {
+        (SolvePath::Double { reason }, backward_error) => {
+            assert!(backward_error < 1e-14);
+            reason
+        }
+        path => panic!("{:?}", path),
+    };
+
+    //cond(A) of the 8 x 8 Hilbert matrix is about 10^10, beyond f32 factors.
+    let hilbert = (0..8)
+        .map(|row| {
+            (0..8)
+                .map(|column| 1.0 / (row + column + 1) as f64)
+                .collect()
+        })
+        .collect();
+    assert_eq!(reason(hilbert), Fallback::NoConvergence);
+    assert_eq!(
+        reason(vec![vec![1.0e40, 1.0], vec![1.0, 1.0]]),
+        Fallback::OutOfRange
+    );
+    //1 + 1e-10 rounds to 1 in f32.
+    assert_eq!(
+        reason(vec![vec![1.0, 1.0], vec![1.0, 1.0 + 1.0e-10]]),
+        Fallback::SingularInSinglePrecision
+    );
+    assert_eq!(
+        fallback(vec![vec![1.0, 2.0], vec![2.0, 4.0]]).unwrap_err(),
+        Error::ZeroPivot { column: 1 }
+    );
+}
+
+///Returns a matrix of values between -1 and 1 from a linear congruential generator.
+fn pseudo_random(rows: usize, columns: usize, seed: u64) -> matrix::Matrix<f64> {
+    let mut state = seed;
+    let mut matrix = matrix::Matrix::new(rows, columns);
+    for row in 0..rows {
+        for column in 0..columns {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            matrix.values[row][column] = (state >> 11) as f64 / (1u64 << 52) as f64 - 1.0;
+        }
+    }
+    matrix
+}
+
+fn multiply(a: &matrix::Matrix<f64>, b: &matrix::Matrix<f64>) -> matrix::Matrix<f64> {
+    let mut product = matrix::Matrix::new(a.rows(), b.columns());
+    for row in 0..a.rows() {
+        for column in 0..b.columns() {
+            for k in 0..a.columns() {
+                product.values[row][column] += a.values[row][k] * b.values[k][column];
+            }
+        }
+    }
+    product
+}
+
+#[test]
+fn rational_determinant_and_inverse_are_exact() {
+    use lu_decomposition::{decompose, LuDecomposition};
+    use matrix::rational::Rational;
+    use matrix::Matrix;
+    let hilbert = |n: usize| {
+        Matrix::from(
+            (0..n)
+                .map(|i| {
+                    (0..n)
+                        .map(|j| Rational::new(1, (i + j + 1) as i64))
+                        .collect()
+                })
+                .collect(),
+        )
+    };
+    let lu = LuDecomposition::new(hilbert(5)).unwrap();
+    assert_eq!(lu.determinant(), Rational::new(1, 266716800000));
+    assert_eq!(lu.inverse().values[4][4], Rational::from(44100));
+
+    //The first non-zero pivot is taken, not the largest.
+    let mut a = Matrix::from(vec![
+        vec![Rational::from(1), Rational::from(2)],
+        vec![Rational::from(3), Rational::from(4)],
+    ]);
+    let (permutation, parity) = decompose(&mut a).unwrap();
+    assert_eq!((permutation, parity), (vec![0, 1], 1));
+
+    //A zero pivot is only passed over, never perturbed, and singular matrices fail.
+    let mut a = Matrix::from(vec![
+        vec![Rational::from(0), Rational::from(2)],
+        vec![Rational::from(3), Rational::from(4)],
+    ]);
+    assert_eq!(decompose(&mut a).unwrap().0, vec![1, 1]);
+    let singular = Matrix::from(vec![
+        vec![Rational::from(1), Rational::from(2)],
+        vec![Rational::from(2), Rational::from(4)],
+    ]);
+    assert!(LuDecomposition::new(singular).is_err());
+}
+
+#[test]
+fn verified_solution_encloses_the_exact_solution() {
+    use lu_decomposition::{solve_verified, Error, LuDecomposition};
+    use matrix::rational::Rational;
+    use matrix::{Field, Matrix, Scalar};
+    let hilbert = |n: usize| {
+        Matrix::from(
+            (0..n)
+                .map(|i| (0..n).map(|j| 1.0 / (i + j + 1) as f64).collect())
+                .collect(),
+        )
+    };
+    //With b = A * [1, -2, 3, ...], computed exactly in f64 for a matrix of small integers.
+    let a: Matrix<f64> = Matrix::from(vec![
+        vec![10.0, -7.0, 0.0, 1.0],
+        vec![-3.0, 2.0, 6.0, 0.0],
+        vec![5.0, -1.0, 5.0, 2.0],
+        vec![1.0, 0.0, 2.0, 9.0],
+    ]);
+    let x = [1.0, -2.0, 3.0, -4.0];
+    let b = Matrix::from(
+        (0..4)
+            .map(|i| vec![(0..4).map(|j| a.values[i][j] * x[j]).sum()])
+            .collect(),
+    );
+    let result = solve_verified(&a, &b).unwrap();
+    for (row, exact) in x.iter().enumerate() {
+        let enclosure = result.enclosure.values[row][0];
+        assert!(enclosure.contains(*exact));
+        assert!(enclosure.width() < 1e-14);
+    }
+
+    //The exact solution for the rounded Hilbert matrix, found in rational arithmetic, lies in the enclosure.
+    let a = hilbert(8);
+    let b = Matrix::from(vec![vec![1.0]; 8]);
+    let result = solve_verified(&a, &b).unwrap();
+    let exact_a = Matrix::from(
+        a.values
+            .iter()
+            .map(|row| row.iter().map(|v| Rational::from_f64(*v)).collect())
+            .collect(),
+    );
+    let exact = LuDecomposition::new(exact_a)
+        .unwrap()
+        .solve(&Matrix::from(vec![vec![Rational::one()]; 8]));
+    for (row, enclosure) in result.enclosure.values.iter().enumerate() {
+        let (lower, upper) = (enclosure[0].lower(), enclosure[0].upper());
+        assert!(Rational::from_f64(lower) <= exact.values[row][0]);
+        assert!(exact.values[row][0] <= Rational::from_f64(upper));
+        assert!(upper - lower < 1e-4 * upper.abs());
+    }
+
+    //Too ill conditioned to verify in f64.
+    let a = hilbert(14);
+    let b = Matrix::from(vec![vec![1.0]; 14]);
+    assert!(matches!(
+        solve_verified(&a, &b),
+        Err(Error::NotVerified { .. })
+    ));
+}
+
+#[test]
+fn transposed_solve_with_every_strategy() {
+    use lu_decomposition::{DecomposeOptions, LuDecomposition};
+    use matrix::pivoting::Pivoting;
+    let a = pseudo_random(6, 6, 11);
+    let b = pseudo_random(6, 2, 12);
+    let mut transposed = matrix::Matrix::new(6, 6);
+    for row in 0..6 {
+        for column in 0..6 {
+            transposed.values[row][column] = a.values[column][row];
+        }
+    }
+    let strategies = [
+        Pivoting::None,
+        Pivoting::Partial,
+        Pivoting::ScaledPartial,
+        Pivoting::Complete,
+        Pivoting::Rook,
+    ];
+    for pivoting in strategies {
+        let options = DecomposeOptions {
+            pivoting,
+            ..DecomposeOptions::default()
+        };
+        let y = LuDecomposition::with_options(a.clone(), options)
+            .unwrap()
+            .solve_transposed(&b);
+        let product = multiply(&transposed, &y);
+        for (row, values) in product.values.iter().enumerate() {
+            for (column, value) in values.iter().enumerate() {
+                assert!(
+                    (value - b.values[row][column]).abs() < 1e-12,
+                    "{:?}",
+                    pivoting
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn dual_solve_matches_the_tangent_and_adjoint() {
+    use lu_decomposition::LuDecomposition;
+    use matrix::dual::Dual;
+    let (a, b) = (pseudo_random(5, 5, 21), pseudo_random(5, 1, 22));
+    let c = pseudo_random(5, 1, 23);
+    let lu = LuDecomposition::new(a.clone()).unwrap();
+    let x = lu.solve(&b);
+    //L = c^T * x, so dL/dx = c.
+    let gradient = lu.adjoint(&x, &c);
+
+    for i in 0..5 {
+        for j in 0..5 {
+            //Seed the derivative of a[i][j], and differentiate through the elimination itself.
+            let mut dual_a = matrix::Matrix::new(5, 5);
+            let mut da = matrix::Matrix::new(5, 5);
+            for row in 0..5 {
+                for column in 0..5 {
+                    dual_a.values[row][column] = Dual::constant(a.values[row][column]);
+                }
+            }
+            dual_a.values[i][j].derivative = 1.0;
+            da.values[i][j] = 1.0;
+            let mut dual_b = matrix::Matrix::new(5, 1);
+            for row in 0..5 {
+                dual_b.values[row][0] = Dual::constant(b.values[row][0]);
+            }
+            let dual_x = LuDecomposition::new(dual_a).unwrap().solve(&dual_b);
+            let dx = lu.tangent(&x, &da, &matrix::Matrix::new(5, 1));
+            let mut dl = 0.0;
+            for row in 0..5 {
+                assert!((dual_x.values[row][0].value - x.values[row][0]).abs() < 1e-12);
+                assert!((dual_x.values[row][0].derivative - dx.values[row][0]).abs() < 1e-9);
+                dl += c.values[row][0] * dual_x.values[row][0].derivative;
+            }
+            assert!((gradient.a.values[i][j] - dl).abs() < 1e-9);
+        }
+    }
+
+    //dx/db is A^-1, so dL/db = A^-T * c.
+    let inverse = lu.inverse();
+    for row in 0..5 {
+        let expected: f64 = (0..5)
+            .map(|k| inverse.values[k][row] * c.values[k][0])
+            .sum();
+        assert!((gradient.b.values[row][0] - expected).abs() < 1e-12);
+    }
+}
+
+///The diagonally dominant 30 x 30 matrix of the flop table in the README.
+fn readme_matrix() -> matrix::Matrix<matrix::count::Counted> {
+    use matrix::count::Counted;
+    let n = 30;
+    let mut a = matrix::Matrix::new(n, n);
+    for row in 0..n {
+        for column in 0..n {
+            a.values[row][column] = Counted(match row == column {
+                true => 100.0,
+                false => ((row * 7 + column * 3) % 11) as f64 / 10.0,
+            });
+        }
+    }
+    a
+}
+
+#[test]
+fn flops_in_the_readme_table() {
+    use lu_decomposition::{crout_with, decompose, decompose_with, CroutOptions, DecomposeOptions};
+    use matrix::count::count_operations;
+    use matrix::pivoting::Pivoting;
+    use matrix::trace::NoTrace;
+    let a = readme_matrix();
+    let decompose_flops = |pivoting: Pivoting| {
+        let options = DecomposeOptions {
+            pivoting,
+            ..DecomposeOptions::default()
+        };
+        let (_, counts) = count_operations(|| {
+            let mut lu = a.clone();
+            decompose_with(&mut lu, options, &mut NoTrace).unwrap()
+        });
+        counts.flops()
+    };
+    assert_eq!(decompose_flops(Pivoting::ScaledPartial), 18_070);
+    assert_eq!(decompose_flops(Pivoting::Partial), 18_040);
+
+    let mut lu = a.clone();
+    let (permutation, _) = decompose(&mut lu).unwrap();
+    let crout_flops = |skip_leading_zeros: bool| {
+        let options = CroutOptions {
+            skip_leading_zeros,
+            ..CroutOptions::default()
+        };
+        let (_, counts) = count_operations(|| {
+            let mut identity = matrix::Matrix::identity(30);
+            crout_with(&lu, &permutation, &mut identity, options, &mut NoTrace);
+            identity
+        });
+        counts.flops()
+    };
+    assert_eq!(crout_flops(true), 44_110);
+    assert_eq!(crout_flops(false), 53_100);
+}
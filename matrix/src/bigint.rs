@@ -0,0 +1,384 @@
+//!Arbitrary precision integers, the numerators and denominators of Rational.
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+///An integer of any size, stored as its sign and its magnitude in base 2^32, least significant digit first.
+/// The magnitude has no leading zero digits, and zero is never negative, so equal values are stored alike.
+///
+///### Usage
+/// ```
+///    use matrix::bigint::BigInt;
+///    let factorial = (1..=25).fold(BigInt::from(1), |product, k| product * BigInt::from(k));
+///    assert_eq!(factorial.to_string(), "15511210043330985984000000");
+///    let (quotient, remainder) = factorial.div_rem(&BigInt::from(-7_000_000_000i64));
+///    assert_eq!(quotient * BigInt::from(-7_000_000_000i64) + remainder, factorial);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    magnitude: Vec<u32>,
+}
+
+impl BigInt {
+    pub fn zero() -> Self {
+        BigInt::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn abs(&self) -> Self {
+        BigInt {
+            negative: false,
+            magnitude: self.magnitude.clone(),
+        }
+    }
+
+    ///The number of bits in the magnitude. Zero has none.
+    pub fn bits(&self) -> usize {
+        match self.magnitude.last() {
+            Some(top) => self.magnitude.len() * 32 - top.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    ///Returns the quotient rounded toward zero, and the remainder, which takes the sign of self.
+    /// Panics if `divisor` is zero.
+    pub fn div_rem(&self, divisor: &BigInt) -> (BigInt, BigInt) {
+        assert!(!divisor.is_zero(), "Division by zero.");
+        let (quotient, remainder) = div_rem_magnitude(&self.magnitude, &divisor.magnitude);
+        (
+            BigInt::signed(self.negative != divisor.negative, quotient),
+            BigInt::signed(self.negative, remainder),
+        )
+    }
+
+    ///The greatest common divisor, by Euclid's algorithm. Never negative. The gcd of zero and zero is zero.
+    pub fn gcd(&self, other: &BigInt) -> BigInt {
+        let (mut a, mut b) = (self.abs(), other.abs());
+        while !b.is_zero() {
+            let remainder = a.div_rem(&b).1;
+            (a, b) = (b, remainder);
+        }
+        a
+    }
+
+    ///Multiplies by 2^`bits`.
+    pub fn shl(&self, bits: usize) -> BigInt {
+        if self.is_zero() {
+            return BigInt::zero();
+        }
+        let (digits, bits) = (bits / 32, bits % 32);
+        let mut magnitude = vec![0; digits];
+        let mut carry = 0u32;
+        for digit in &self.magnitude {
+            let shifted = ((*digit as u64) << bits) | carry as u64;
+            magnitude.push(shifted as u32);
+            carry = (shifted >> 32) as u32;
+        }
+        magnitude.push(carry);
+        BigInt::signed(self.negative, magnitude)
+    }
+
+    ///Divides by 2^`bits`, rounding the magnitude down.
+    pub fn shr(&self, bits: usize) -> BigInt {
+        let (digits, bits) = (bits / 32, bits % 32);
+        if digits >= self.magnitude.len() {
+            return BigInt::zero();
+        }
+        let high = |index: usize| self.magnitude.get(index + 1).copied().unwrap_or(0) as u64;
+        let magnitude = (digits..self.magnitude.len())
+            .map(|index| (((high(index) << 32) | self.magnitude[index] as u64) >> bits) as u32)
+            .collect();
+        BigInt::signed(self.negative, magnitude)
+    }
+
+    ///An approximate f64, or an infinity if the magnitude is too large. Each digit is added with its own rounding,
+    /// so the result may be a few units in the last place from the nearest f64.
+    pub fn to_f64(&self) -> f64 {
+        let magnitude = self
+            .magnitude
+            .iter()
+            .rev()
+            .fold(0.0, |value, digit| value * 4294967296.0 + *digit as f64);
+        if self.negative {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+
+    ///Builds a value from a sign and a magnitude that may have leading zeros.
+    fn signed(negative: bool, magnitude: Vec<u32>) -> BigInt {
+        let magnitude = trimmed(magnitude);
+        BigInt {
+            negative: negative && !magnitude.is_empty(),
+            magnitude,
+        }
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(value: i64) -> Self {
+        let magnitude = value.unsigned_abs();
+        BigInt::signed(value < 0, vec![magnitude as u32, (magnitude >> 32) as u32])
+    }
+}
+
+impl From<u64> for BigInt {
+    fn from(value: u64) -> Self {
+        BigInt::signed(false, vec![value as u32, (value >> 32) as u32])
+    }
+}
+
+impl From<i32> for BigInt {
+    fn from(value: i32) -> Self {
+        BigInt::from(value as i64)
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => compare_magnitude(&self.magnitude, &other.magnitude),
+            (true, true) => compare_magnitude(&other.magnitude, &self.magnitude),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+    fn neg(self) -> BigInt {
+        BigInt::signed(!self.negative, self.magnitude)
+    }
+}
+
+impl Add for BigInt {
+    type Output = BigInt;
+    fn add(self, other: BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::signed(
+                self.negative,
+                add_magnitude(&self.magnitude, &other.magnitude),
+            );
+        }
+        //Opposite signs: subtract the smaller magnitude from the larger, and keep the sign of the larger.
+        match compare_magnitude(&self.magnitude, &other.magnitude) {
+            Ordering::Less => BigInt::signed(
+                other.negative,
+                subtract_magnitude(&other.magnitude, &self.magnitude),
+            ),
+            _ => BigInt::signed(
+                self.negative,
+                subtract_magnitude(&self.magnitude, &other.magnitude),
+            ),
+        }
+    }
+}
+
+impl Sub for BigInt {
+    type Output = BigInt;
+    fn sub(self, other: BigInt) -> BigInt {
+        self + -other
+    }
+}
+
+impl Mul for BigInt {
+    type Output = BigInt;
+    fn mul(self, other: BigInt) -> BigInt {
+        let negative = self.negative != other.negative;
+        BigInt::signed(
+            negative,
+            multiply_magnitude(&self.magnitude, &other.magnitude),
+        )
+    }
+}
+
+impl fmt::Display for BigInt {
+    ///Writes the value in decimal, nine digits at a time.
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_zero() {
+            return write!(formatter, "0");
+        }
+        let billion = [1_000_000_000];
+        let mut groups = Vec::new();
+        let mut magnitude = self.magnitude.clone();
+        while !magnitude.is_empty() {
+            let (quotient, remainder) = div_rem_magnitude(&magnitude, &billion);
+            groups.push(remainder.first().copied().unwrap_or(0));
+            magnitude = quotient;
+        }
+        let mut text = String::from(if self.negative { "-" } else { "" });
+        text += &groups.pop().unwrap_or(0).to_string();
+        for group in groups.iter().rev() {
+            text += &format!("{:09}", group);
+        }
+        formatter.pad(&text)
+    }
+}
+
+fn compare_magnitude(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut sum = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0u64;
+    for index in 0..a.len().max(b.len()) {
+        let digit = |digits: &[u32]| digits.get(index).copied().unwrap_or(0) as u64;
+        let total = digit(a) + digit(b) + carry;
+        sum.push(total as u32);
+        carry = total >> 32;
+    }
+    sum.push(carry as u32);
+    sum
+}
+
+///Returns a - b, where a >= b.
+fn subtract_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut difference = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (index, digit) in a.iter().enumerate() {
+        let mut total = *digit as i64 - b.get(index).copied().unwrap_or(0) as i64 - borrow;
+        borrow = 0;
+        if total < 0 {
+            total += 1 << 32;
+            borrow = 1;
+        }
+        difference.push(total as u32);
+    }
+    difference
+}
+
+///Long multiplication, one digit of b at a time.
+fn multiply_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut product = vec![0u32; a.len() + b.len()];
+    for (i, x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, y) in b.iter().enumerate() {
+            let total = *x as u64 * *y as u64 + product[i + j] as u64 + carry;
+            product[i + j] = total as u32;
+            carry = total >> 32;
+        }
+        product[i + b.len()] = carry as u32;
+    }
+    product
+}
+
+///Returns the quotient and remainder of the magnitudes. Divides by a single digit directly, and otherwise
+/// by Knuth's algorithm D, The Art of Computer Programming volume 2, section 4.3.1: one quotient digit per step,
+/// estimated from the top digits and corrected at most twice.
+fn div_rem_magnitude(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if compare_magnitude(a, b) == Ordering::Less {
+        return (Vec::new(), a.to_vec());
+    }
+    if let [divisor] = b {
+        let divisor = *divisor as u64;
+        let mut quotient = vec![0u32; a.len()];
+        let mut remainder = 0u64;
+        for index in (0..a.len()).rev() {
+            let current = (remainder << 32) | a[index] as u64;
+            quotient[index] = (current / divisor) as u32;
+            remainder = current % divisor;
+        }
+        return (trimmed(quotient), trimmed(vec![remainder as u32]));
+    }
+    //Normalize, so that the top digit of the divisor has its top bit set. The estimates are then close.
+    let shift = b[b.len() - 1].leading_zeros();
+    let mut divisor = shift_left(b, shift);
+    divisor.pop();
+    let mut remainder = shift_left(a, shift);
+    let (n, base) = (divisor.len(), 1u64 << 32);
+    let (top, next) = (divisor[n - 1] as u64, divisor[n - 2] as u128);
+    let mut quotient = vec![0u32; a.len() - n + 1];
+    for j in (0..quotient.len()).rev() {
+        let leading = ((remainder[j + n] as u64) << 32) | remainder[j + n - 1] as u64;
+        let (mut estimate, mut rest) = (leading / top, leading % top);
+        //Too large by at most two. The next digit of each finds all but the rarest overestimate.
+        while estimate >= base
+            || estimate as u128 * next > ((rest as u128) << 32 | remainder[j + n - 2] as u128)
+        {
+            estimate -= 1;
+            rest += top;
+            if rest >= base {
+                break;
+            }
+        }
+        //Subtract estimate * divisor from the remainder, starting at digit j.
+        let (mut borrow, mut carry) = (0i64, 0u64);
+        for (i, digit) in divisor.iter().enumerate() {
+            let product = estimate * *digit as u64 + carry;
+            carry = product >> 32;
+            let difference = remainder[i + j] as i64 - borrow - (product as u32) as i64;
+            remainder[i + j] = difference as u32;
+            borrow = (difference < 0) as i64;
+        }
+        let difference = remainder[j + n] as i64 - borrow - carry as i64;
+        remainder[j + n] = difference as u32;
+        //The rare overestimate: the remainder went negative, so add the divisor back once.
+        if difference < 0 {
+            estimate -= 1;
+            let mut carry = 0u64;
+            for (i, digit) in divisor.iter().enumerate() {
+                let sum = remainder[i + j] as u64 + *digit as u64 + carry;
+                remainder[i + j] = sum as u32;
+                carry = sum >> 32;
+            }
+            remainder[j + n] = remainder[j + n].wrapping_add(carry as u32);
+        }
+        quotient[j] = estimate as u32;
+    }
+    remainder.truncate(n);
+    (trimmed(quotient), trimmed(shift_right(&remainder, shift)))
+}
+
+///Shifts the magnitude left by fewer than 32 bits, into one more digit than it had.
+fn shift_left(magnitude: &[u32], bits: u32) -> Vec<u32> {
+    let mut shifted = Vec::with_capacity(magnitude.len() + 1);
+    let mut carry = 0u32;
+    for digit in magnitude {
+        let wide = ((*digit as u64) << bits) | carry as u64;
+        shifted.push(wide as u32);
+        carry = (wide >> 32) as u32;
+    }
+    shifted.push(carry);
+    shifted
+}
+
+///Shifts the magnitude right by fewer than 32 bits.
+fn shift_right(magnitude: &[u32], bits: u32) -> Vec<u32> {
+    if bits == 0 {
+        return magnitude.to_vec();
+    }
+    let high = |index: usize| {
+        magnitude
+            .get(index + 1)
+            .map_or(0, |above| above << (32 - bits))
+    };
+    (0..magnitude.len())
+        .map(|index| (magnitude[index] >> bits) | high(index))
+        .collect()
+}
+
+fn trimmed(mut magnitude: Vec<u32>) -> Vec<u32> {
+    while magnitude.last() == Some(&0) {
+        magnitude.pop();
+    }
+    magnitude
+}
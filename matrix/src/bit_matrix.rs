@@ -0,0 +1,142 @@
+//!Matrices over GF(2), packed 64 elements to a word.
+
+///A matrix over GF(2), the field of 0 and 1 where addition is exclusive or. Each row is packed into 64 bit words,
+/// least significant bit first, so adding one row to another is an exclusive or of their words.
+///
+///### Usage
+/// ```
+///    use matrix::bit_matrix::BitMatrix;
+///    let mut a = BitMatrix::from(vec![vec![1, 1, 0], vec![0, 1, 1]]);
+///    a.add_row(0, 1);
+///    assert_eq!(a.row(1), vec![1, 0, 1]);
+///    assert!(a.get(1, 2));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitMatrix {
+    words: Vec<Vec<u64>>,
+    rows: usize,
+    columns: usize,
+}
+
+impl BitMatrix {
+    ///Return a zeroed matrix.
+    pub fn new(rows: usize, columns: usize) -> Self {
+        BitMatrix {
+            words: vec![vec![0; columns.div_ceil(64)]; rows],
+            rows,
+            columns,
+        }
+    }
+
+    ///Return an identity matrix of size x size.
+    pub fn identity(size: usize) -> Self {
+        let mut matrix = BitMatrix::new(size, size);
+        for x in 0..size {
+            matrix.set(x, x, true);
+        }
+        matrix
+    }
+
+    ///Builds a matrix from rows of 0 and 1. Any odd value is 1. Panics if the rows differ in length.
+    pub fn from(values: Vec<Vec<u8>>) -> Self {
+        let columns = values.first().map_or(0, |row| row.len());
+        let mut matrix = BitMatrix::new(values.len(), columns);
+        for (row, values) in values.iter().enumerate() {
+            assert_eq!(values.len(), columns, "Rows have differing lengths");
+            for (column, value) in values.iter().enumerate() {
+                matrix.set(row, column, value % 2 == 1);
+            }
+        }
+        matrix
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> bool {
+        assert!(column < self.columns, "Column {} is out of bounds.", column);
+        (self.words[row][column / 64] >> (column % 64)) & 1 == 1
+    }
+
+    pub fn set(&mut self, row: usize, column: usize, value: bool) {
+        assert!(column < self.columns, "Column {} is out of bounds.", column);
+        let mask = 1 << (column % 64);
+        if value {
+            self.words[row][column / 64] |= mask;
+        } else {
+            self.words[row][column / 64] &= !mask;
+        }
+    }
+
+    ///The elements of `row`, as 0 and 1.
+    pub fn row(&self, row: usize) -> Vec<u8> {
+        (0..self.columns)
+            .map(|column| self.get(row, column) as u8)
+            .collect()
+    }
+
+    ///The packed words of `row`. Bits past the last column are zero.
+    pub fn row_words(&self, row: usize) -> &[u64] {
+        &self.words[row]
+    }
+
+    ///Takes a tuple (usize, usize) and swaps the rows. Will panic if index is out of bounds.
+    pub fn swap_rows(&mut self, rows: (usize, usize)) {
+        self.words.swap(rows.0, rows.1);
+    }
+
+    ///Adds row `source` to row `target`, a word at a time. Subtraction is the same operation in GF(2).
+    pub fn add_row(&mut self, source: usize, target: usize) {
+        if source == target {
+            self.words[target].iter_mut().for_each(|word| *word = 0);
+            return;
+        }
+        let (source, target) = match source < target {
+            true => {
+                let (above, below) = self.words.split_at_mut(target);
+                (&above[source], &mut below[0])
+            }
+            false => {
+                let (above, below) = self.words.split_at_mut(source);
+                (&below[0], &mut above[target])
+            }
+        };
+        for (target, source) in target.iter_mut().zip(source) {
+            *target ^= source;
+        }
+    }
+
+    ///Returns the matrix with the columns of `other` appended to the right of each row.
+    pub fn augment(&self, other: &BitMatrix) -> BitMatrix {
+        assert_eq!(
+            self.rows, other.rows,
+            "Augmenting with a different number of rows."
+        );
+        let mut augmented = BitMatrix::new(self.rows, self.columns + other.columns);
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                augmented.set(row, column, self.get(row, column));
+            }
+            for column in 0..other.columns {
+                augmented.set(row, self.columns + column, other.get(row, column));
+            }
+        }
+        augmented
+    }
+
+    ///Returns columns `start..end` of every row.
+    pub fn columns_range(&self, start: usize, end: usize) -> BitMatrix {
+        let mut part = BitMatrix::new(self.rows, end - start);
+        for row in 0..self.rows {
+            for column in start..end {
+                part.set(row, column - start, self.get(row, column));
+            }
+        }
+        part
+    }
+}
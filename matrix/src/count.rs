@@ -0,0 +1,181 @@
+use crate::{Field, Scalar};
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+///The number of floating point operations performed, by kind.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Counts {
+    ///Additions and subtractions.
+    pub additions: u64,
+    pub multiplications: u64,
+    pub divisions: u64,
+    ///Comparisons for equality or order.
+    pub comparisons: u64,
+    pub square_roots: u64,
+}
+
+impl Counts {
+    ///Returns the number of additions, multiplications, divisions and square roots.
+    /// Comparisons are not floating point operations.
+    pub fn flops(&self) -> u64 {
+        self.additions + self.multiplications + self.divisions + self.square_roots
+    }
+}
+
+impl Sub for Counts {
+    type Output = Counts;
+    fn sub(self, rhs: Counts) -> Counts {
+        Counts {
+            additions: self.additions - rhs.additions,
+            multiplications: self.multiplications - rhs.multiplications,
+            divisions: self.divisions - rhs.divisions,
+            comparisons: self.comparisons - rhs.comparisons,
+            square_roots: self.square_roots - rhs.square_roots,
+        }
+    }
+}
+
+thread_local! {
+    static COUNTS: Cell<Counts> = Cell::new(Counts::default());
+}
+
+///Adds one to the count selected by `kind`.
+fn tally(kind: fn(&mut Counts) -> &mut u64) {
+    COUNTS.with(|counts| {
+        let mut current = counts.get();
+        *kind(&mut current) += 1;
+        counts.set(current);
+    });
+}
+
+///Runs `solver` and returns its result with the operations performed on Counted values along the way.
+///
+///### Usage
+/// Counts are kept per thread. Operations performed on other threads are not included.
+/// ```
+///    use matrix::count::{count_operations, Counted};
+///    let (product, counts) = count_operations(|| Counted(2.0) * Counted(3.0) + Counted(1.0));
+///    assert_eq!(product, Counted(7.0));
+///    assert_eq!((counts.multiplications, counts.additions), (1, 1));
+/// ```
+pub fn count_operations<R>(solver: impl FnOnce() -> R) -> (R, Counts) {
+    let before = COUNTS.with(|counts| counts.get());
+    let result = solver();
+    let after = COUNTS.with(|counts| counts.get());
+    (result, after - before)
+}
+
+///An f64 that counts the operations performed on it. Put it in a Matrix and run a solver inside count_operations().
+///
+/// Negation, absolute values and conversions are free. Everything else is counted once.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Counted(pub f64);
+
+impl PartialEq for Counted {
+    fn eq(&self, other: &Self) -> bool {
+        tally(|counts| &mut counts.comparisons);
+        self.0 == other.0
+    }
+}
+
+impl PartialOrd for Counted {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        tally(|counts| &mut counts.comparisons);
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+impl Add for Counted {
+    type Output = Counted;
+    fn add(self, rhs: Counted) -> Counted {
+        tally(|counts| &mut counts.additions);
+        Counted(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Counted {
+    type Output = Counted;
+    fn sub(self, rhs: Counted) -> Counted {
+        tally(|counts| &mut counts.additions);
+        Counted(self.0 - rhs.0)
+    }
+}
+
+impl Mul for Counted {
+    type Output = Counted;
+    fn mul(self, rhs: Counted) -> Counted {
+        tally(|counts| &mut counts.multiplications);
+        Counted(self.0 * rhs.0)
+    }
+}
+
+impl Div for Counted {
+    type Output = Counted;
+    fn div(self, rhs: Counted) -> Counted {
+        tally(|counts| &mut counts.divisions);
+        Counted(self.0 / rhs.0)
+    }
+}
+
+impl Neg for Counted {
+    type Output = Counted;
+    fn neg(self) -> Counted {
+        Counted(-self.0)
+    }
+}
+
+impl AddAssign for Counted {
+    fn add_assign(&mut self, rhs: Counted) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Counted {
+    fn sub_assign(&mut self, rhs: Counted) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for Counted {
+    fn mul_assign(&mut self, rhs: Counted) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign for Counted {
+    fn div_assign(&mut self, rhs: Counted) {
+        *self = *self / rhs;
+    }
+}
+
+impl Field for Counted {
+    fn zero() -> Self {
+        Counted(0.0)
+    }
+    fn one() -> Self {
+        Counted(1.0)
+    }
+}
+
+impl Scalar for Counted {
+    fn abs(&self) -> Self {
+        Counted(self.0.abs())
+    }
+    fn is_finite(&self) -> bool {
+        self.0.is_finite()
+    }
+    fn from_f64(value: f64) -> Self {
+        Counted(value)
+    }
+    fn to_f64(&self) -> f64 {
+        self.0
+    }
+    fn epsilon() -> Self {
+        Counted(f64::EPSILON)
+    }
+    fn sqrt(&self) -> Self {
+        tally(|counts| &mut counts.square_roots);
+        Counted(self.0.sqrt())
+    }
+}
@@ -0,0 +1,163 @@
+//!Dual numbers, an element type that carries a derivative through every operation, for forward mode differentiation.
+use crate::{Field, Scalar};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+///The number value + derivative * ε, where ε * ε = 0. Arithmetic on dual numbers applies the chain rule,
+/// so the derivative of a result is the derivative of the computation along the direction seeded in its inputs.
+///
+///### Usage
+/// Seed the derivative of each input with its rate of change along one direction, then read the derivative of each output.
+/// A solve over dual numbers differentiates through every step of the elimination; LuDecomposition::tangent gets the same
+/// derivative from the factors of A.
+/// ```
+///    use matrix::dual::Dual;
+///    let x = Dual::variable(3.0);
+///    let y = x * x * x - Dual::constant(2.0) * x;
+///    assert_eq!(y, Dual::new(21.0, 25.0));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Dual {
+    pub value: f64,
+    pub derivative: f64,
+}
+
+impl Dual {
+    pub fn new(value: f64, derivative: f64) -> Self {
+        Dual { value, derivative }
+    }
+
+    ///A value that does not change along the direction.
+    pub fn constant(value: f64) -> Self {
+        Dual::new(value, 0.0)
+    }
+
+    ///The variable the derivative is taken with respect to.
+    pub fn variable(value: f64) -> Self {
+        Dual::new(value, 1.0)
+    }
+}
+
+impl PartialOrd for Dual {
+    ///Orders by value. Equal values with different derivatives are unordered, so that the order agrees with ==.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.value.partial_cmp(&other.value)? {
+            Ordering::Equal if self.derivative != other.derivative => None,
+            ordering => Some(ordering),
+        }
+    }
+}
+
+impl Neg for Dual {
+    type Output = Dual;
+    fn neg(self) -> Dual {
+        Dual::new(-self.value, -self.derivative)
+    }
+}
+
+impl Add for Dual {
+    type Output = Dual;
+    fn add(self, other: Dual) -> Dual {
+        Dual::new(self.value + other.value, self.derivative + other.derivative)
+    }
+}
+
+impl Sub for Dual {
+    type Output = Dual;
+    fn sub(self, other: Dual) -> Dual {
+        Dual::new(self.value - other.value, self.derivative - other.derivative)
+    }
+}
+
+impl Mul for Dual {
+    type Output = Dual;
+    ///The product rule.
+    fn mul(self, other: Dual) -> Dual {
+        Dual::new(
+            self.value * other.value,
+            self.derivative * other.value + self.value * other.derivative,
+        )
+    }
+}
+
+impl Div for Dual {
+    type Output = Dual;
+    ///The quotient rule.
+    fn div(self, other: Dual) -> Dual {
+        let value = self.value / other.value;
+        Dual::new(
+            value,
+            (self.derivative - value * other.derivative) / other.value,
+        )
+    }
+}
+
+impl AddAssign for Dual {
+    fn add_assign(&mut self, other: Dual) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Dual {
+    fn sub_assign(&mut self, other: Dual) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign for Dual {
+    fn mul_assign(&mut self, other: Dual) {
+        *self = *self * other;
+    }
+}
+
+impl DivAssign for Dual {
+    fn div_assign(&mut self, other: Dual) {
+        *self = *self / other;
+    }
+}
+
+impl fmt::Display for Dual {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.pad(&format!("{} + {}ε", self.value, self.derivative))
+    }
+}
+
+impl Field for Dual {
+    fn zero() -> Self {
+        Dual::constant(0.0)
+    }
+    fn one() -> Self {
+        Dual::constant(1.0)
+    }
+}
+
+impl Scalar for Dual {
+    ///Differentiable away from zero. At zero, takes the derivative of the positive side.
+    fn abs(&self) -> Self {
+        if self.value < 0.0 {
+            -*self
+        } else {
+            *self
+        }
+    }
+    fn is_finite(&self) -> bool {
+        self.value.is_finite() && self.derivative.is_finite()
+    }
+    ///A constant.
+    fn from_f64(value: f64) -> Self {
+        Dual::constant(value)
+    }
+    ///The value. The derivative is dropped.
+    fn to_f64(&self) -> f64 {
+        self.value
+    }
+    fn epsilon() -> Self {
+        Dual::constant(f64::EPSILON)
+    }
+    ///Not differentiable at zero, where the derivative is infinite.
+    fn sqrt(&self) -> Self {
+        let value = self.value.sqrt();
+        Dual::new(value, self.derivative / (2.0 * value))
+    }
+}
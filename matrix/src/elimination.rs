@@ -0,0 +1,140 @@
+//!What the elimination crates share: the errors of solving A * X = B, the checks made before any row is touched,
+//! and the operations whose choice depends on the element type.
+use crate::field::PrimeField;
+use crate::kernels::{self, plain};
+use crate::pivoting::{self, first_non_zero, Pivoting};
+use crate::{Field, Matrix, Scalar};
+
+///The operations of an elimination that depend on the element type, so that one elimination serves every Field.
+///
+///### Usage
+/// Every Scalar chooses its pivots by size, through pivoting::select_pivot, and may use the vectorized kernels.
+/// A field with no order, such as PrimeField, has no rounding error for a large pivot to limit, so it takes the first
+/// non-zero candidate whatever the strategy, and the plain loops.
+/// ```
+///    use matrix::elimination::Eliminate;
+///    use matrix::field::PrimeField;
+///    use matrix::pivoting::Pivoting;
+///    type F7 = PrimeField<7>;
+///    let a = matrix::Matrix::from(vec![vec![F7::new(0), F7::new(1)], vec![F7::new(2), F7::new(6)]]);
+///    let location = F7::select_pivot(&a, &[0, 1], &[0, 1], Pivoting::Complete, &[]);
+///    assert_eq!(location, Some((1, 0)));
+/// ```
+pub trait Eliminate: Field {
+    ///Returns false for NaN and the infinities. Only a Scalar can hold them.
+    fn finite(value: &Self) -> bool;
+
+    ///The reciprocal of the largest absolute value in each row of A, the weights of Pivoting::ScaledPartial.
+    /// A zero row, and every row of a field with no magnitude, weighs one.
+    fn row_scales(a: &Matrix<Self>) -> Vec<Self>;
+
+    ///Returns the location (row, column) of the next pivot among the candidate `rows` and `columns`,
+    /// or None when it would be zero. See pivoting::select_pivot for the arguments.
+    fn select_pivot(
+        a: &Matrix<Self>,
+        rows: &[usize],
+        columns: &[usize],
+        pivoting: Pivoting,
+        scales: &[Self],
+    ) -> Option<(usize, usize)>;
+
+    ///Multiplies every value by `factor`. See kernels::scale.
+    fn scale(values: &mut [Self], factor: &Self, simd: bool);
+
+    ///Subtracts `factor` times each element of `source` from the matching element of `target`.
+    /// See kernels::subtract_multiple.
+    fn subtract_multiple(target: &mut [Self], factor: &Self, source: &[Self], simd: bool);
+}
+
+impl<T: Scalar> Eliminate for T {
+    fn finite(value: &Self) -> bool {
+        value.is_finite()
+    }
+    fn row_scales(a: &Matrix<Self>) -> Vec<Self> {
+        let mut scales = vec![T::one(); a.rows()];
+        for (row, scale) in scales.iter_mut().enumerate() {
+            let max = a.row_abs_max(row).0;
+            if max != T::zero() {
+                *scale = T::one() / max;
+            }
+        }
+        scales
+    }
+    fn select_pivot(
+        a: &Matrix<Self>,
+        rows: &[usize],
+        columns: &[usize],
+        pivoting: Pivoting,
+        scales: &[Self],
+    ) -> Option<(usize, usize)> {
+        pivoting::select_pivot(a, rows, columns, pivoting, scales)
+    }
+    fn scale(values: &mut [Self], factor: &Self, simd: bool) {
+        kernels::scale(values, factor, simd);
+    }
+    fn subtract_multiple(target: &mut [Self], factor: &Self, source: &[Self], simd: bool) {
+        kernels::subtract_multiple(target, factor, source, simd);
+    }
+}
+
+impl<const P: u64> Eliminate for PrimeField<P> {
+    fn finite(_value: &Self) -> bool {
+        true
+    }
+    fn row_scales(a: &Matrix<Self>) -> Vec<Self> {
+        vec![Self::one(); a.rows()]
+    }
+    fn select_pivot(
+        a: &Matrix<Self>,
+        rows: &[usize],
+        columns: &[usize],
+        pivoting: Pivoting,
+        _scales: &[Self],
+    ) -> Option<(usize, usize)> {
+        match pivoting {
+            Pivoting::Complete | Pivoting::Rook => first_non_zero(a, rows, columns),
+            _ => first_non_zero(a, rows, columns.get(..1)?),
+        }
+    }
+    fn scale(values: &mut [Self], factor: &Self, _simd: bool) {
+        plain::scale(values, factor);
+    }
+    fn subtract_multiple(target: &mut [Self], factor: &Self, source: &[Self], _simd: bool) {
+        plain::subtract_multiple(target, factor, source);
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    ///Every remaining candidate pivot was zero at elimination step `step`.
+    SingularMatrix { step: usize },
+    ///A has `a_rows` rows, but B has `b_rows` rows.
+    DimensionMismatch { a_rows: usize, b_rows: usize },
+    ///A is not square, or its rows differ in length.
+    NotSquare { rows: usize, columns: usize },
+    ///A or B holds a NaN or an infinity.
+    NonFiniteInput,
+}
+
+///Checks everything that can be checked before elimination begins: A is square, B has as many rows,
+/// and both are finite.
+///
+///### Errors
+/// `NotSquare`, then `DimensionMismatch`, then `NonFiniteInput`, the first that applies.
+pub fn validate<T: Eliminate>(a: &Matrix<T>, b: &Matrix<T>) -> Result<(), Error> {
+    let (rows, columns) = (a.rows(), a.columns());
+    if rows != columns || a.is_mangled() {
+        return Err(Error::NotSquare { rows, columns });
+    }
+    if b.rows() != rows {
+        return Err(Error::DimensionMismatch {
+            a_rows: rows,
+            b_rows: b.rows(),
+        });
+    }
+    let finite = |m: &Matrix<T>| m.values.iter().flatten().all(T::finite);
+    if !finite(a) || !finite(b) {
+        return Err(Error::NonFiniteInput);
+    }
+    Ok(())
+}
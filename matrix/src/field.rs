@@ -0,0 +1,169 @@
+//!Fields, the arithmetic a Matrix needs, and the prime fields GF(p).
+use std::fmt;
+use std::fmt::Debug;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+///The element type of a Matrix: a set with addition, subtraction, multiplication and division by any non-zero element.
+/// Implemented for every Scalar, and for PrimeField, which has no order or magnitude.
+///
+/// Requires Clone rather than Copy, so that element types that own memory,
+/// such as arbitrary precision numbers, can implement it too.
+/// Requires Send and Sync, so that the columns of a right hand side can be solved on separate threads.
+pub trait Field:
+    Clone
+    + Send
+    + Sync
+    + Debug
+    + PartialEq
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+{
+    ///The additive identity.
+    fn zero() -> Self;
+
+    ///The multiplicative identity.
+    fn one() -> Self;
+}
+
+///An element of GF(P), the integers modulo the prime P. Stored reduced, in 0..P.
+///
+///### Usage
+/// P must be prime for every non-zero element to have an inverse. Division by an element with no inverse panics,
+/// which is how a composite P is found. P must be less than 2^63.
+/// ```
+///    use matrix::field::PrimeField;
+///    type F7 = PrimeField<7>;
+///    assert_eq!(F7::new(3) * F7::new(5), F7::new(1));
+///    assert_eq!(F7::new(1) / F7::new(3), F7::new(5));
+///    assert_eq!(-F7::new(2), F7::new(5));
+///    assert_eq!(F7::from_signed(-9), F7::new(5));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PrimeField<const P: u64>(u64);
+
+impl<const P: u64> PrimeField<P> {
+    const VALID_MODULUS: () = assert!(
+        P >= 2 && P < 1 << 63,
+        "P must be at least 2 and less than 2^63."
+    );
+
+    ///Returns `value` modulo P.
+    pub fn new(value: u64) -> Self {
+        let () = Self::VALID_MODULUS;
+        PrimeField(value % P)
+    }
+
+    ///Returns `value` modulo P, in 0..P for negative values too.
+    pub fn from_signed(value: i64) -> Self {
+        PrimeField::new(value.rem_euclid(P as i64) as u64)
+    }
+
+    ///The representative in 0..P.
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    ///The multiplicative inverse, by the extended Euclidean algorithm. None for zero, or for an element
+    /// that shares a factor with a composite P.
+    pub fn inverse(&self) -> Option<Self> {
+        let (mut r, mut next_r) = (P as i128, self.0 as i128);
+        let (mut t, mut next_t) = (0i128, 1i128);
+        while next_r != 0 {
+            let quotient = r / next_r;
+            (r, next_r) = (next_r, r - quotient * next_r);
+            (t, next_t) = (next_t, t - quotient * next_t);
+        }
+        (r == 1).then(|| PrimeField(t.rem_euclid(P as i128) as u64))
+    }
+}
+
+impl<const P: u64> Field for PrimeField<P> {
+    fn zero() -> Self {
+        PrimeField::new(0)
+    }
+    fn one() -> Self {
+        PrimeField::new(1)
+    }
+}
+
+impl<const P: u64> Neg for PrimeField<P> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        PrimeField((P - self.0) % P)
+    }
+}
+
+impl<const P: u64> Add for PrimeField<P> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        //Both are below 2^63, so the sum does not overflow.
+        PrimeField((self.0 + other.0) % P)
+    }
+}
+
+impl<const P: u64> Sub for PrimeField<P> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl<const P: u64> Mul for PrimeField<P> {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        PrimeField(multiply::<P>(self.0, other.0))
+    }
+}
+
+impl<const P: u64> Div for PrimeField<P> {
+    type Output = Self;
+    ///Panics if `other` has no inverse.
+    fn div(self, other: Self) -> Self {
+        match other.inverse() {
+            Some(inverse) => PrimeField(multiply::<P>(self.0, inverse.0)),
+            None => panic!("{} has no inverse modulo {}.", other.0, P),
+        }
+    }
+}
+
+impl<const P: u64> AddAssign for PrimeField<P> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<const P: u64> SubAssign for PrimeField<P> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<const P: u64> MulAssign for PrimeField<P> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl<const P: u64> DivAssign for PrimeField<P> {
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
+impl<const P: u64> fmt::Display for PrimeField<P> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, formatter)
+    }
+}
+
+///Returns a * b modulo P, in 128 bits so that the product does not overflow.
+fn multiply<const P: u64>(a: u64, b: u64) -> u64 {
+    (a as u128 * b as u128 % P as u128) as u64
+}
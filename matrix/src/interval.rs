@@ -0,0 +1,315 @@
+//!Intervals of f64 with outward rounding, an element type whose results are guaranteed to contain the exact result.
+//!
+//! Each bound is rounded away from the interval: the lower bound down and the upper bound up. Rust has no control
+//! of the rounding mode, so each operation is done in round to nearest, and the rounding error found exactly by an
+//! error free transformation, TwoSum for addition and a fused multiply add for multiplication and division.
+//! A bound that was rounded toward the interval is moved out by one unit in the last place.
+use crate::{Field, Scalar};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+///The closed interval [lower, upper] of the extended reals.
+///
+///### Usage
+/// ```
+///    use matrix::interval::Interval;
+///    let tenth = Interval::point(1.0) / Interval::point(10.0);
+///    assert!(tenth.lower() < tenth.upper());
+///    let sum = (0..10).fold(Interval::point(0.0), |sum, _| sum + tenth);
+///    assert!(sum.contains(1.0));
+///    assert!(sum.width() < 1e-14);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Interval {
+    lower: f64,
+    upper: f64,
+}
+
+impl Interval {
+    ///Panics if `lower` is greater than `upper`, or either is NaN.
+    pub fn new(lower: f64, upper: f64) -> Self {
+        assert!(lower <= upper, "[{}, {}] is not an interval.", lower, upper);
+        Interval { lower, upper }
+    }
+
+    ///The interval holding only `value`.
+    pub fn point(value: f64) -> Self {
+        Interval::new(value, value)
+    }
+
+    ///The whole real line, the result of dividing by an interval that holds zero.
+    pub fn entire() -> Self {
+        Interval::new(f64::NEG_INFINITY, f64::INFINITY)
+    }
+
+    pub fn lower(&self) -> f64 {
+        self.lower
+    }
+
+    pub fn upper(&self) -> f64 {
+        self.upper
+    }
+
+    ///The midpoint, rounded to nearest. It lies in the interval.
+    pub fn midpoint(&self) -> f64 {
+        match (self.lower.is_finite(), self.upper.is_finite()) {
+            (true, true) => self.lower / 2.0 + self.upper / 2.0,
+            _ if self.lower == self.upper => self.lower,
+            _ => 0.0,
+        }
+    }
+
+    ///The width, rounded up.
+    pub fn width(&self) -> f64 {
+        sum(self.upper, -self.lower).1
+    }
+
+    pub fn contains(&self, value: f64) -> bool {
+        self.lower <= value && value <= self.upper
+    }
+
+    ///True if `other` lies in the interior of self, the test of Krawczyk's method.
+    pub fn contains_in_interior(&self, other: &Interval) -> bool {
+        self.lower < other.lower && other.upper < self.upper
+    }
+
+    ///The smallest interval holding both.
+    pub fn hull(&self, other: &Interval) -> Interval {
+        Interval::new(self.lower.min(other.lower), self.upper.max(other.upper))
+    }
+}
+
+impl PartialOrd for Interval {
+    ///Intervals are ordered when one lies wholly below the other. Overlapping intervals are unordered,
+    /// unless they are equal.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self == other {
+            Some(Ordering::Equal)
+        } else if self.upper < other.lower {
+            Some(Ordering::Less)
+        } else if self.lower > other.upper {
+            Some(Ordering::Greater)
+        } else {
+            None
+        }
+    }
+}
+
+impl Neg for Interval {
+    type Output = Interval;
+    fn neg(self) -> Interval {
+        Interval::new(-self.upper, -self.lower)
+    }
+}
+
+impl Add for Interval {
+    type Output = Interval;
+    fn add(self, other: Interval) -> Interval {
+        Interval::new(
+            sum(self.lower, other.lower).0,
+            sum(self.upper, other.upper).1,
+        )
+    }
+}
+
+impl Sub for Interval {
+    type Output = Interval;
+    fn sub(self, other: Interval) -> Interval {
+        self + -other
+    }
+}
+
+impl Mul for Interval {
+    type Output = Interval;
+    ///The least and greatest of the products of the bounds.
+    fn mul(self, other: Interval) -> Interval {
+        let products = [
+            product(self.lower, other.lower),
+            product(self.lower, other.upper),
+            product(self.upper, other.lower),
+            product(self.upper, other.upper),
+        ];
+        let lower = products
+            .iter()
+            .map(|bounds| bounds.0)
+            .fold(f64::INFINITY, f64::min);
+        let upper = products
+            .iter()
+            .map(|bounds| bounds.1)
+            .fold(f64::NEG_INFINITY, f64::max);
+        Interval::new(lower, upper)
+    }
+}
+
+impl Div for Interval {
+    type Output = Interval;
+    ///The least and greatest of the quotients of the bounds. Dividing by an interval that holds zero gives the entire line.
+    fn div(self, other: Interval) -> Interval {
+        if other.contains(0.0) {
+            return Interval::entire();
+        }
+        let quotients = [
+            quotient(self.lower, other.lower),
+            quotient(self.lower, other.upper),
+            quotient(self.upper, other.lower),
+            quotient(self.upper, other.upper),
+        ];
+        let lower = quotients
+            .iter()
+            .map(|bounds| bounds.0)
+            .fold(f64::INFINITY, f64::min);
+        let upper = quotients
+            .iter()
+            .map(|bounds| bounds.1)
+            .fold(f64::NEG_INFINITY, f64::max);
+        Interval::new(lower, upper)
+    }
+}
+
+impl AddAssign for Interval {
+    fn add_assign(&mut self, other: Interval) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Interval {
+    fn sub_assign(&mut self, other: Interval) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign for Interval {
+    fn mul_assign(&mut self, other: Interval) {
+        *self = *self * other;
+    }
+}
+
+impl DivAssign for Interval {
+    fn div_assign(&mut self, other: Interval) {
+        *self = *self / other;
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.pad(&format!("[{:e}, {:e}]", self.lower, self.upper))
+    }
+}
+
+impl Field for Interval {
+    fn zero() -> Self {
+        Interval::point(0.0)
+    }
+    fn one() -> Self {
+        Interval::point(1.0)
+    }
+}
+
+impl Scalar for Interval {
+    ///The interval of the absolute values of the members.
+    fn abs(&self) -> Self {
+        if self.lower >= 0.0 {
+            *self
+        } else if self.upper <= 0.0 {
+            -*self
+        } else {
+            Interval::new(0.0, self.upper.max(-self.lower))
+        }
+    }
+    fn is_finite(&self) -> bool {
+        self.lower.is_finite() && self.upper.is_finite()
+    }
+    ///The point interval. Panics on NaN.
+    fn from_f64(value: f64) -> Self {
+        Interval::point(value)
+    }
+    ///The midpoint.
+    fn to_f64(&self) -> f64 {
+        self.midpoint()
+    }
+    fn epsilon() -> Self {
+        Interval::point(f64::EPSILON)
+    }
+    ///The square roots of the non-negative members. The square root is correctly rounded,
+    /// so a step outward from each bound encloses it. Panics if every member is negative.
+    fn sqrt(&self) -> Self {
+        assert!(self.upper >= 0.0, "{:?} has no real square root.", self);
+        let lower = match self.lower > 0.0 {
+            true => self.lower.sqrt().next_down().max(0.0),
+            false => 0.0,
+        };
+        Interval::new(lower, self.upper.sqrt().next_up())
+    }
+}
+
+///Below this magnitude a product or quotient may have lost bits to underflow, so its error cannot be found exactly.
+const UNDERFLOW: f64 = 1.0e-290;
+
+///Returns a + b rounded down and rounded up.
+fn sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    if !s.is_finite() {
+        return overflowed(s, a.is_finite() && b.is_finite());
+    }
+    //TwoSum: the rounding error of s, exactly.
+    let b_virtual = s - a;
+    let error = (a - (s - b_virtual)) + (b - b_virtual);
+    directed(s, error)
+}
+
+///Returns a * b rounded down and rounded up. Zero times an infinity is zero, as the bounds of an interval.
+fn product(a: f64, b: f64) -> (f64, f64) {
+    if a == 0.0 || b == 0.0 {
+        return (0.0, 0.0);
+    }
+    let p = a * b;
+    if !p.is_finite() {
+        return overflowed(p, a.is_finite() && b.is_finite());
+    }
+    if p.abs() < UNDERFLOW {
+        return (p.next_down(), p.next_up());
+    }
+    directed(p, a.mul_add(b, -p))
+}
+
+///Returns a / b rounded down and rounded up, for b not zero.
+fn quotient(a: f64, b: f64) -> (f64, f64) {
+    if a == 0.0 || (b.is_infinite() && a.is_finite()) {
+        return (0.0, 0.0);
+    }
+    let q = a / b;
+    if !q.is_finite() {
+        return overflowed(q, a.is_finite());
+    }
+    if q.abs() < UNDERFLOW || a.abs() < UNDERFLOW {
+        return (q.next_down(), q.next_up());
+    }
+    //a - q * b, exactly. The exact quotient is q + remainder / b.
+    let remainder = (-q).mul_add(b, a);
+    directed(q, remainder * b.signum())
+}
+
+///Moves `value` out by one unit in the last place on the side the exact result lies, by the sign of `error`.
+fn directed(value: f64, error: f64) -> (f64, f64) {
+    if error > 0.0 {
+        (value, value.next_up())
+    } else if error < 0.0 {
+        (value.next_down(), value)
+    } else {
+        (value, value)
+    }
+}
+
+///The bounds of an infinite result, which is only exact if an operand was infinite.
+/// An infinity divided by an infinity may be anything.
+fn overflowed(value: f64, finite_operands: bool) -> (f64, f64) {
+    if value.is_nan() {
+        return (f64::NEG_INFINITY, f64::INFINITY);
+    }
+    match (finite_operands, value > 0.0) {
+        (true, true) => (f64::MAX, f64::INFINITY),
+        (true, false) => (f64::NEG_INFINITY, f64::MIN),
+        (false, _) => (value, value),
+    }
+}
@@ -0,0 +1,230 @@
+//!The innermost loops of the algorithms, over contiguous slices, in a plain and a vectorized form.
+//!
+//! Each kernel takes a `simd` flag, so that the two forms can be compared on the same input.
+//! The vectorized forms are methods of Scalar. Their defaults are the plain loops. f32 and f64 use
+//! AVX instructions on x86_64 processors that have them, detected when the kernel runs, and the plain loops otherwise.
+//!
+//! scale, add and subtract_multiple round every element exactly as the plain loops do, so their results are identical.
+//! dot_subtract sums its products in several lanes at once, so its result may differ in the last bits.
+use crate::Scalar;
+
+///Multiplies every value by `factor`.
+pub fn scale<T: Scalar>(values: &mut [T], factor: &T, simd: bool) {
+    if simd {
+        T::vectorized_scale(values, factor);
+    } else {
+        plain::scale(values, factor);
+    }
+}
+
+///Adds `addend` to every value.
+pub fn add<T: Scalar>(values: &mut [T], addend: &T, simd: bool) {
+    if simd {
+        T::vectorized_add(values, addend);
+    } else {
+        plain::add(values, addend);
+    }
+}
+
+///Subtracts `factor` times each element of `source` from the matching element of `target`.
+/// Stops at the end of the shorter slice.
+pub fn subtract_multiple<T: Scalar>(target: &mut [T], factor: &T, source: &[T], simd: bool) {
+    if simd {
+        T::vectorized_subtract_multiple(target, factor, source);
+    } else {
+        plain::subtract_multiple(target, factor, source);
+    }
+}
+
+///Returns `initial` less the dot product of `x` and `y`. Stops at the end of the shorter slice.
+///
+///### Usage
+/// The summations of Crout's algorithm and of substitution, `sum -= a[row][k] * a[k][column]`, once the column
+/// is gathered into a slice.
+/// ```
+///    use matrix::kernels::dot_subtract;
+///    let x = [1.0, 2.0, 3.0, 4.0, 5.0];
+///    let y = [1.0, 1.0, 1.0, 1.0, 2.0];
+///    assert_eq!(dot_subtract(20.0, &x, &y, false), 0.0);
+///    assert_eq!(dot_subtract(20.0, &x, &y, true), 0.0);
+/// ```
+pub fn dot_subtract<T: Scalar>(initial: T, x: &[T], y: &[T], simd: bool) -> T {
+    if simd {
+        T::vectorized_dot_subtract(initial, x, y)
+    } else {
+        plain::dot_subtract(initial, x, y)
+    }
+}
+
+///The plain loops, one element at a time.
+pub(crate) mod plain {
+    use crate::Field;
+
+    pub fn scale<T: Field>(values: &mut [T], factor: &T) {
+        for value in values {
+            *value *= factor.clone();
+        }
+    }
+
+    pub fn add<T: Field>(values: &mut [T], addend: &T) {
+        for value in values {
+            *value += addend.clone();
+        }
+    }
+
+    pub fn subtract_multiple<T: Field>(target: &mut [T], factor: &T, source: &[T]) {
+        for (element, source) in target.iter_mut().zip(source) {
+            *element -= factor.clone() * source.clone();
+        }
+    }
+
+    pub fn dot_subtract<T: Field>(initial: T, x: &[T], y: &[T]) -> T {
+        let mut sum = initial;
+        for (x, y) in x.iter().zip(y) {
+            sum -= x.clone() * y.clone();
+        }
+        sum
+    }
+}
+
+///Generates the vectorized kernels of one floating point type, and their dispatch on the processor's features.
+macro_rules! vectorized {
+    ($module:ident, $float:ty, $lanes:expr, $vector:ident,
+     $load:ident, $store:ident, $set1:ident, $setzero:ident, $add:ident, $sub:ident, $mul:ident) => {
+        pub(crate) mod $module {
+            use super::plain;
+
+            pub fn scale(values: &mut [$float], factor: $float) {
+                #[cfg(target_arch = "x86_64")]
+                if std::arch::is_x86_feature_detected!("avx") {
+                    //SAFETY: the processor supports AVX, checked above.
+                    unsafe { avx::scale(values, factor) };
+                    return;
+                }
+                plain::scale(values, &factor);
+            }
+
+            pub fn add(values: &mut [$float], addend: $float) {
+                #[cfg(target_arch = "x86_64")]
+                if std::arch::is_x86_feature_detected!("avx") {
+                    //SAFETY: the processor supports AVX, checked above.
+                    unsafe { avx::add(values, addend) };
+                    return;
+                }
+                plain::add(values, &addend);
+            }
+
+            pub fn subtract_multiple(target: &mut [$float], factor: $float, source: &[$float]) {
+                #[cfg(target_arch = "x86_64")]
+                if std::arch::is_x86_feature_detected!("avx") {
+                    //SAFETY: the processor supports AVX, checked above.
+                    unsafe { avx::subtract_multiple(target, factor, source) };
+                    return;
+                }
+                plain::subtract_multiple(target, &factor, source);
+            }
+
+            pub fn dot_subtract(initial: $float, x: &[$float], y: &[$float]) -> $float {
+                #[cfg(target_arch = "x86_64")]
+                if std::arch::is_x86_feature_detected!("avx") {
+                    //SAFETY: the processor supports AVX, checked above.
+                    return unsafe { avx::dot_subtract(initial, x, y) };
+                }
+                plain::dot_subtract(initial, x, y)
+            }
+
+            ///Each function works on whole vectors of lanes, then leaves the remainder to the plain loop.
+            /// Loads and stores are unaligned, so any slice will do.
+            #[cfg(target_arch = "x86_64")]
+            mod avx {
+                use super::plain;
+                use std::arch::x86_64::*;
+
+                const LANES: usize = $lanes;
+
+                #[target_feature(enable = "avx")]
+                pub unsafe fn scale(values: &mut [$float], factor: $float) {
+                    let factors = $set1(factor);
+                    let mut chunks = values.chunks_exact_mut(LANES);
+                    for chunk in &mut chunks {
+                        let pointer = chunk.as_mut_ptr();
+                        $store(pointer, $mul($load(pointer), factors));
+                    }
+                    plain::scale(chunks.into_remainder(), &factor);
+                }
+
+                #[target_feature(enable = "avx")]
+                pub unsafe fn add(values: &mut [$float], addend: $float) {
+                    let addends = $set1(addend);
+                    let mut chunks = values.chunks_exact_mut(LANES);
+                    for chunk in &mut chunks {
+                        let pointer = chunk.as_mut_ptr();
+                        $store(pointer, $add($load(pointer), addends));
+                    }
+                    plain::add(chunks.into_remainder(), &addend);
+                }
+
+                #[target_feature(enable = "avx")]
+                pub unsafe fn subtract_multiple(
+                    target: &mut [$float],
+                    factor: $float,
+                    source: &[$float],
+                ) {
+                    let length = target.len().min(source.len());
+                    let (target, source) = (&mut target[..length], &source[..length]);
+                    let factors = $set1(factor);
+                    let vectors = length / LANES * LANES;
+                    for start in (0..vectors).step_by(LANES) {
+                        let pointer = target[start..].as_mut_ptr();
+                        let product = $mul(factors, $load(source[start..].as_ptr()));
+                        $store(pointer, $sub($load(pointer), product));
+                    }
+                    plain::subtract_multiple(&mut target[vectors..], &factor, &source[vectors..]);
+                }
+
+                #[target_feature(enable = "avx")]
+                pub unsafe fn dot_subtract(initial: $float, x: &[$float], y: &[$float]) -> $float {
+                    let length = x.len().min(y.len());
+                    let vectors = length / LANES * LANES;
+                    let mut sums: $vector = $setzero();
+                    for start in (0..vectors).step_by(LANES) {
+                        let product = $mul($load(x[start..].as_ptr()), $load(y[start..].as_ptr()));
+                        sums = $add(sums, product);
+                    }
+                    let mut lanes = [0.0; LANES];
+                    $store(lanes.as_mut_ptr(), sums);
+                    let sum: $float = lanes.iter().sum();
+                    plain::dot_subtract(initial - sum, &x[vectors..length], &y[vectors..length])
+                }
+            }
+        }
+    };
+}
+
+vectorized!(
+    f64_kernels,
+    f64,
+    4,
+    __m256d,
+    _mm256_loadu_pd,
+    _mm256_storeu_pd,
+    _mm256_set1_pd,
+    _mm256_setzero_pd,
+    _mm256_add_pd,
+    _mm256_sub_pd,
+    _mm256_mul_pd
+);
+
+vectorized!(
+    f32_kernels,
+    f32,
+    8,
+    __m256,
+    _mm256_loadu_ps,
+    _mm256_storeu_ps,
+    _mm256_set1_ps,
+    _mm256_setzero_ps,
+    _mm256_add_ps,
+    _mm256_sub_ps,
+    _mm256_mul_ps
+);
@@ -0,0 +1,508 @@
+//!Linear least squares: the x that minimizes ||A * x - b||_2 for an m x n A with m >= n.
+use crate::{Matrix, Scalar};
+
+///The largest number of sweeps of the one-sided Jacobi SVD. Each sweep rotates every pair of columns once,
+/// and convergence is quadratic, so a handful is usual.
+const MAX_SWEEPS: usize = 60;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    ///A and b have different numbers of rows.
+    DimensionMismatch { a_rows: usize, b_rows: usize },
+    ///A has fewer rows than columns, so the solution is not determined by the fit.
+    Underdetermined { rows: usize, columns: usize },
+    ///Column `column` of A is within the tolerance of the span of the columns before it, so the normal equations
+    /// and QR have no unique solution. LeastSquaresMethod::Svd returns the solution of least norm instead.
+    RankDeficient { column: usize },
+}
+
+///How least_squares solves the problem. From the fastest and least accurate to the slowest and most robust.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LeastSquaresMethod {
+    ///Solves A^T * A * x = A^T * b by Cholesky. About m * n^2 / 2 flops, half those of QR,
+    /// but A^T * A has the square of the condition number of A, so half the digits are lost first.
+    /// Reports a SquaredConditionWarning when that matters. See LeastSquaresOptions::condition_limit.
+    NormalEquations,
+    ///Householder QR of A. Backward stable, and the choice of LAPACK's dgels.
+    Qr,
+    ///The one-sided Jacobi singular value decomposition of A. Singular values within the tolerance are dropped,
+    /// so a rank deficient A gives the solution of least norm instead of an error.
+    Svd,
+}
+
+///The choices least_squares_with may make.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LeastSquaresOptions {
+    pub method: LeastSquaresMethod,
+    ///The relative size below which a column is taken to be dependent on the others. Compared with the pivots of
+    /// the Cholesky factorization against the largest diagonal element of A^T * A, with the diagonal of R against the
+    /// largest column norm of A, or with the singular values against the largest. None takes max(m, n) * epsilon.
+    pub tolerance: Option<f64>,
+    ///The square of the condition number of A above which the normal equations report a SquaredConditionWarning.
+    /// None takes 1 / sqrt(epsilon), where half the digits of the solution are lost. Exact types, whose epsilon
+    /// is zero, and an infinite limit are never warned about.
+    pub condition_limit: Option<f64>,
+}
+
+impl Default for LeastSquaresOptions {
+    fn default() -> Self {
+        LeastSquaresOptions {
+            method: LeastSquaresMethod::Qr,
+            tolerance: None,
+            condition_limit: None,
+        }
+    }
+}
+
+///Reported by the normal equations when the condition number of A^T * A, the square of that of A, exceeds the limit.
+/// QR or the SVD, which work with A itself, would lose half as many digits.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SquaredConditionWarning<T = f32> {
+    ///The estimated 2-norm condition number of A.
+    pub condition: T,
+    ///The condition number of A^T * A, condition squared.
+    pub squared: T,
+    pub limit: T,
+}
+
+///The fit returned by least_squares.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LeastSquares<T = f32> {
+    ///The n x k solution X, a column for each column of b.
+    pub solution: Matrix<T>,
+    ///||A * x - b||_2^2 for each column of b, from the residual of the solution.
+    pub residual_sum_of_squares: Vec<T>,
+    ///s^2 * (A^T * A)^-1 for each column of b, with s^2 = RSS / (m - rank), the covariance of the parameters
+    /// when the errors in b are independent with equal variance. None when m equals the rank, leaving no degrees of freedom.
+    /// With the SVD, (A^T * A)^-1 is the pseudo-inverse over the singular values kept.
+    pub covariance: Option<Vec<Matrix<T>>>,
+    ///The number of independent columns. n, except with the SVD.
+    pub rank: usize,
+    ///The 2-norm condition number of A. Exact with the SVD, over the singular values kept. Otherwise estimated
+    /// as sqrt(||A^T * A||_1 * ||(A^T * A)^-1||_1), within a factor of sqrt(n).
+    pub condition: T,
+    pub method: LeastSquaresMethod,
+    ///Some if the normal equations squared the condition number past LeastSquaresOptions::condition_limit.
+    pub warning: Option<SquaredConditionWarning<T>>,
+}
+
+impl<T: Scalar> Matrix<T> {
+    ///Fits A * x = b in the least squares sense, by Householder QR. See least_squares_with().
+    ///
+    ///### Usage
+    /// ```
+    ///    let a = matrix::Matrix::from(vec![vec![1.0, 0.0], vec![1.0, 1.0], vec![1.0, 2.0], vec![1.0, 3.0]]);
+    ///    let b = matrix::Matrix::from(vec![vec![1.0], vec![3.0], vec![5.0], vec![7.0]]);
+    ///    let fit = a.least_squares(&b).unwrap();
+    ///    //y = 1 + 2 t, exactly.
+    ///    assert!((fit.solution.values[0][0] - 1.0f64).abs() < 1.0e-12);
+    ///    assert!((fit.solution.values[1][0] - 2.0f64).abs() < 1.0e-12);
+    ///    assert!(fit.residual_sum_of_squares[0] < 1.0e-24);
+    /// ```
+    ///### Errors
+    /// See least_squares_with().
+    pub fn least_squares(&self, b: &Matrix<T>) -> Result<LeastSquares<T>, Error> {
+        self.least_squares_with(b, LeastSquaresOptions::default())
+    }
+
+    ///Fits A * x = b in the least squares sense by the method of `options`, with the residual sum of squares
+    /// and the covariance of the parameters. Every method takes square roots, so an exact type such as Rational
+    /// gives a result that is only as exact as its square roots. See Scalar::sqrt.
+    ///
+    ///### Arguments
+    /// * `b`: the m x k right hand sides, fitted independently.
+    /// * `options`: the method, the tolerance for rank deficiency and the limit for a SquaredConditionWarning.
+    ///
+    ///### Usage
+    /// ```
+    ///    use matrix::least_squares::{LeastSquaresMethod, LeastSquaresOptions};
+    ///    //A monomial basis on [0, 1] is badly conditioned. Its normal equations are worse.
+    ///    let a = matrix::Matrix::from((0..20).map(|i| {
+    ///        let t = i as f64 / 19.0;
+    ///        (0..8).map(|power| t.powi(power)).collect()
+    ///    }).collect());
+    ///    let b = matrix::Matrix::from((0..20).map(|i| vec![(i as f64 / 19.0).exp()]).collect());
+    ///    let options = LeastSquaresOptions { method: LeastSquaresMethod::NormalEquations, ..Default::default() };
+    ///    let fit = a.least_squares_with(&b, options).unwrap();
+    ///    let warning = fit.warning.unwrap();
+    ///    assert!(warning.squared > warning.limit);
+    /// ```
+    ///### Errors
+    /// * `DimensionMismatch` if A and b have different numbers of rows.
+    /// * `Underdetermined` if A has fewer rows than columns.
+    /// * `RankDeficient` if a column of A depends on the others, with the normal equations or QR.
+    pub fn least_squares_with(
+        &self,
+        b: &Matrix<T>,
+        options: LeastSquaresOptions,
+    ) -> Result<LeastSquares<T>, Error> {
+        let (m, n) = (self.rows(), self.columns());
+        if b.rows() != m {
+            let (a_rows, b_rows) = (m, b.rows());
+            return Err(Error::DimensionMismatch { a_rows, b_rows });
+        }
+        if m < n {
+            return Err(Error::Underdetermined {
+                rows: m,
+                columns: n,
+            });
+        }
+        let tolerance = T::from_f64(
+            options
+                .tolerance
+                .unwrap_or(m.max(n) as f64 * T::epsilon().to_f64()),
+        );
+
+        let (solution, unscaled_covariance, rank, singular_condition) = match options.method {
+            LeastSquaresMethod::NormalEquations => {
+                let (solution, inverse) = normal_equations(self, b, &tolerance)?;
+                (solution, inverse, n, None)
+            }
+            LeastSquaresMethod::Qr => {
+                let (solution, inverse) = householder(self, b, &tolerance)?;
+                (solution, inverse, n, None)
+            }
+            LeastSquaresMethod::Svd => {
+                let (solution, inverse, rank, condition) = singular_values(self, b, &tolerance);
+                (solution, inverse, rank, Some(condition))
+            }
+        };
+        let condition = singular_condition.unwrap_or_else(|| {
+            let gram = transpose_multiply(self, self);
+            (gram.norm_one() * unscaled_covariance.norm_one()).sqrt()
+        });
+
+        //Exact types have an epsilon of zero, and lose no digits by squaring the condition number.
+        let limit = match options.condition_limit {
+            Some(limit) => Some(limit),
+            None if T::epsilon() > T::zero() => Some(1.0 / T::epsilon().to_f64().sqrt()),
+            None => None,
+        };
+        let limit = limit.filter(|limit| limit.is_finite()).map(T::from_f64);
+        let mut warning = None;
+        if let (LeastSquaresMethod::NormalEquations, Some(limit)) = (options.method, limit) {
+            let squared = condition.clone() * condition.clone();
+            if squared > limit {
+                warning = Some(SquaredConditionWarning {
+                    condition: condition.clone(),
+                    squared,
+                    limit,
+                });
+            }
+        }
+
+        let residual_sum_of_squares: Vec<T> = (0..b.columns())
+            .map(|column| {
+                let mut sum = T::zero();
+                for row in 0..m {
+                    let mut residual = b.values[row][column].clone();
+                    for i in 0..n {
+                        residual -=
+                            self.values[row][i].clone() * solution.values[i][column].clone();
+                    }
+                    sum += residual.clone() * residual;
+                }
+                sum
+            })
+            .collect();
+        let covariance = (m > rank).then(|| {
+            let degrees_of_freedom = T::from_f64((m - rank) as f64);
+            residual_sum_of_squares
+                .iter()
+                .map(|sum| {
+                    let variance = sum.clone() / degrees_of_freedom.clone();
+                    let mut covariance = unscaled_covariance.clone();
+                    for row in covariance.values.iter_mut() {
+                        for value in row.iter_mut() {
+                            *value *= variance.clone();
+                        }
+                    }
+                    covariance
+                })
+                .collect()
+        });
+
+        Ok(LeastSquares {
+            solution,
+            residual_sum_of_squares,
+            covariance,
+            rank,
+            condition,
+            method: options.method,
+            warning,
+        })
+    }
+}
+
+///Returns A^T * B.
+fn transpose_multiply<T: Scalar>(a: &Matrix<T>, b: &Matrix<T>) -> Matrix<T> {
+    let mut product = Matrix::new(a.columns(), b.columns());
+    for row in 0..a.columns() {
+        for column in 0..b.columns() {
+            let mut sum = T::zero();
+            for i in 0..a.rows() {
+                sum += a.values[i][row].clone() * b.values[i][column].clone();
+            }
+            product.values[row][column] = sum;
+        }
+    }
+    product
+}
+
+///Solves A^T * A * x = A^T * b by the Cholesky factorization A^T * A = L * L^T.
+/// Returns x and (A^T * A)^-1.
+fn normal_equations<T: Scalar>(
+    a: &Matrix<T>,
+    b: &Matrix<T>,
+    tolerance: &T,
+) -> Result<(Matrix<T>, Matrix<T>), Error> {
+    let gram = transpose_multiply(a, a);
+    let n = gram.rows();
+    let mut largest = T::zero();
+    for x in 0..n {
+        if gram.values[x][x] > largest {
+            largest = gram.values[x][x].clone();
+        }
+    }
+    let mut lower = Matrix::<T>::new(n, n);
+    '_factor: for column in 0..n {
+        let mut pivot = gram.values[column][column].clone();
+        for k in 0..column {
+            pivot -= lower.values[column][k].clone() * lower.values[column][k].clone();
+        }
+        //The pivot is the squared norm of the part of the column outside the span of those before it.
+        if pivot <= tolerance.clone() * largest.clone() || largest == T::zero() {
+            return Err(Error::RankDeficient { column });
+        }
+        let diagonal = pivot.sqrt();
+        for row in column + 1..n {
+            let mut sum = gram.values[row][column].clone();
+            for k in 0..column {
+                sum -= lower.values[row][k].clone() * lower.values[column][k].clone();
+            }
+            lower.values[row][column] = sum / diagonal.clone();
+        }
+        lower.values[column][column] = diagonal;
+    }
+
+    let cholesky_solve = |rhs: &Matrix<T>| {
+        let mut x = rhs.clone();
+        for column in 0..x.columns() {
+            for row in 0..n {
+                let mut sum = x.values[row][column].clone();
+                for k in 0..row {
+                    sum -= lower.values[row][k].clone() * x.values[k][column].clone();
+                }
+                x.values[row][column] = sum / lower.values[row][row].clone();
+            }
+            for row in (0..n).rev() {
+                let mut sum = x.values[row][column].clone();
+                for k in row + 1..n {
+                    sum -= lower.values[k][row].clone() * x.values[k][column].clone();
+                }
+                x.values[row][column] = sum / lower.values[row][row].clone();
+            }
+        }
+        x
+    };
+    let solution = cholesky_solve(&transpose_multiply(a, b));
+    let inverse = cholesky_solve(&Matrix::identity(n));
+    Ok((solution, inverse))
+}
+
+///Solves by the Householder QR factorization A = Q * R, applying each reflection to b as it is made.
+/// Returns x and (A^T * A)^-1 = R^-1 * R^-T.
+fn householder<T: Scalar>(
+    a: &Matrix<T>,
+    b: &Matrix<T>,
+    tolerance: &T,
+) -> Result<(Matrix<T>, Matrix<T>), Error> {
+    let (m, n, k) = (a.rows(), a.columns(), b.columns());
+    let mut r = a.clone();
+    let mut qtb = b.clone();
+    let column_norm = |column: usize, from: usize, r: &Matrix<T>| {
+        let mut sum = T::zero();
+        for row in from..m {
+            sum += r.values[row][column].clone() * r.values[row][column].clone();
+        }
+        sum.sqrt()
+    };
+    let mut largest = T::zero();
+    for column in 0..n {
+        let norm = column_norm(column, 0, &r);
+        if norm > largest {
+            largest = norm;
+        }
+    }
+
+    '_reflect: for column in 0..n {
+        let norm = column_norm(column, column, &r);
+        if norm <= tolerance.clone() * largest.clone() || largest == T::zero() {
+            return Err(Error::RankDeficient { column });
+        }
+        //Reflect onto -sign(a_jj) * e_1, so that forming v subtracts nothing close to itself.
+        let alpha = match r.values[column][column] > T::zero() {
+            true => -norm,
+            false => norm,
+        };
+        let mut v: Vec<T> = (column..m)
+            .map(|row| r.values[row][column].clone())
+            .collect();
+        v[0] -= alpha.clone();
+        let mut vtv = T::zero();
+        for value in &v {
+            vtv += value.clone() * value.clone();
+        }
+        let reflect = |values: &mut Vec<Vec<T>>, target: usize| {
+            let mut dot = T::zero();
+            for (i, value) in v.iter().enumerate() {
+                dot += value.clone() * values[column + i][target].clone();
+            }
+            let factor = (dot.clone() + dot) / vtv.clone();
+            for (i, value) in v.iter().enumerate() {
+                values[column + i][target] -= factor.clone() * value.clone();
+            }
+        };
+        for target in column + 1..n {
+            reflect(&mut r.values, target);
+        }
+        for target in 0..k {
+            reflect(&mut qtb.values, target);
+        }
+        r.values[column][column] = alpha;
+        for row in column + 1..m {
+            r.values[row][column] = T::zero();
+        }
+    }
+
+    let mut solution = Matrix::<T>::new(n, k);
+    for column in 0..k {
+        for row in (0..n).rev() {
+            let mut sum = qtb.values[row][column].clone();
+            for i in row + 1..n {
+                sum -= r.values[row][i].clone() * solution.values[i][column].clone();
+            }
+            solution.values[row][column] = sum / r.values[row][row].clone();
+        }
+    }
+    //R^-1, column by column, then R^-1 * R^-T.
+    let mut r_inverse = Matrix::<T>::new(n, n);
+    for column in 0..n {
+        for row in (0..=column).rev() {
+            let mut sum = match row == column {
+                true => T::one(),
+                false => T::zero(),
+            };
+            for i in row + 1..=column {
+                sum -= r.values[row][i].clone() * r_inverse.values[i][column].clone();
+            }
+            r_inverse.values[row][column] = sum / r.values[row][row].clone();
+        }
+    }
+    let mut inverse = Matrix::new(n, n);
+    for row in 0..n {
+        for column in 0..n {
+            let mut sum = T::zero();
+            for i in row.max(column)..n {
+                sum += r_inverse.values[row][i].clone() * r_inverse.values[column][i].clone();
+            }
+            inverse.values[row][column] = sum;
+        }
+    }
+    Ok((solution, inverse))
+}
+
+///Solves by the one-sided Jacobi SVD of Hestenes: plane rotations V orthogonalize the columns of A * V = U * S.
+/// Returns the solution of least norm over the singular values above the tolerance, the pseudo-inverse of A^T * A,
+/// the number of singular values kept, and the ratio of the largest to the smallest of them.
+fn singular_values<T: Scalar>(
+    a: &Matrix<T>,
+    b: &Matrix<T>,
+    tolerance: &T,
+) -> (Matrix<T>, Matrix<T>, usize, T) {
+    let (m, n, k) = (a.rows(), a.columns(), b.columns());
+    let mut u = a.clone();
+    let mut v = Matrix::<T>::identity(n);
+    let dot = |u: &Matrix<T>, p: usize, q: usize| {
+        let mut sum = T::zero();
+        for row in 0..m {
+            sum += u.values[row][p].clone() * u.values[row][q].clone();
+        }
+        sum
+    };
+    let rotate = |values: &mut Vec<Vec<T>>, p: usize, q: usize, c: &T, s: &T| {
+        for row in values.iter_mut() {
+            let (x, y) = (row[p].clone(), row[q].clone());
+            row[p] = c.clone() * x.clone() - s.clone() * y.clone();
+            row[q] = s.clone() * x + c.clone() * y;
+        }
+    };
+
+    '_sweep: for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+        for p in 0..n {
+            for q in p + 1..n {
+                let (alpha, beta, gamma) = (dot(&u, p, p), dot(&u, q, q), dot(&u, p, q));
+                //Columns p and q are orthogonal to working precision.
+                if gamma.abs() <= T::epsilon() * (alpha.clone() * beta.clone()).sqrt() {
+                    continue;
+                }
+                rotated = true;
+                let zeta = (beta - alpha) / (gamma.clone() + gamma);
+                let t = T::one() / (zeta.abs() + (T::one() + zeta.clone() * zeta.clone()).sqrt());
+                let t = match zeta < T::zero() {
+                    true => -t,
+                    false => t,
+                };
+                let c = T::one() / (T::one() + t.clone() * t.clone()).sqrt();
+                let s = c.clone() * t;
+                rotate(&mut u.values, p, q, &c, &s);
+                rotate(&mut v.values, p, q, &c, &s);
+            }
+        }
+        if !rotated {
+            break '_sweep;
+        }
+    }
+
+    let sigma: Vec<T> = (0..n)
+        .map(|column| dot(&u, column, column).sqrt())
+        .collect();
+    let mut largest = T::zero();
+    for value in &sigma {
+        if *value > largest {
+            largest = value.clone();
+        }
+    }
+    let kept: Vec<usize> = (0..n)
+        .filter(|column| sigma[*column] > tolerance.clone() * largest.clone())
+        .collect();
+    let mut smallest = largest.clone();
+    for column in &kept {
+        if sigma[*column] < smallest {
+            smallest = sigma[*column].clone();
+        }
+    }
+    let condition = match kept.is_empty() {
+        true => T::one(),
+        false => largest / smallest,
+    };
+
+    //x = sum of v_j * (u_j^T * b) / sigma_j^2, where u_j is column j of A * V, of length sigma_j.
+    let utb = transpose_multiply(&u, b);
+    let mut solution = Matrix::new(n, k);
+    let mut inverse = Matrix::new(n, n);
+    for j in kept.iter() {
+        let squared = sigma[*j].clone() * sigma[*j].clone();
+        for row in 0..n {
+            let weight = v.values[row][*j].clone() / squared.clone();
+            for column in 0..k {
+                solution.values[row][column] += weight.clone() * utb.values[*j][column].clone();
+            }
+            for column in 0..n {
+                inverse.values[row][column] += weight.clone() * v.values[column][*j].clone();
+            }
+        }
+    }
+    (solution, inverse, kept.len(), condition)
+}
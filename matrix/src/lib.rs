@@ -1,15 +1,34 @@
-///A nested vector of type f32.
+pub mod bigint;
+pub mod bit_matrix;
+pub mod count;
+pub mod dual;
+pub mod elimination;
+pub mod field;
+pub mod interval;
+pub mod kernels;
+pub mod least_squares;
+pub mod pivoting;
+pub mod rational;
+pub mod render;
+mod scalar;
+pub mod trace;
+pub mod verify;
+
+pub use field::Field;
+pub use scalar::Scalar;
+
+///A nested vector of type T, f32 unless stated otherwise.
 /// ### Arguments
 ///
-#[derive(Debug, PartialEq)]
-pub struct Matrix {
-    pub values: Vec<Vec<f32>>,
+#[derive(Clone, Debug, PartialEq)]
+pub struct Matrix<T = f32> {
+    pub values: Vec<Vec<T>>,
     rows: usize,
     columns: usize,
 }
 
-impl Matrix {
-    pub fn from(values: Vec<Vec<f32>>) -> Self {
+impl<T: Field> Matrix<T> {
+    pub fn from(values: Vec<Vec<T>>) -> Self {
         let number_of_rows = values.len();
         let number_of_elements = values[0].len();
 
@@ -25,19 +44,19 @@ impl Matrix {
 
     pub fn new(rows: usize, columns: usize) -> Self {
         Matrix {
-            values: vec![vec![0.0; columns.into()]; rows.into()],
+            values: vec![vec![T::zero(); columns]; rows],
             rows,
             columns,
         }
     }
-    pub fn values(&self) -> &Vec<Vec<f32>> {
+    pub fn values(&self) -> &Vec<Vec<T>> {
         &self.values
     }
 
-    ///
+    ///Return a zeroed matrix of size x size
     pub fn square(size: usize) -> Self {
         Matrix {
-            values: vec![vec![0.0; size.into()]; size.into()],
+            values: vec![vec![T::zero(); size]; size],
             rows: size,
             columns: size,
         }
@@ -47,9 +66,9 @@ impl Matrix {
     pub fn identity(size: usize) -> Self {
         let mut matrix = Matrix::square(size);
         for x in 0..matrix.rows() {
-            matrix.values[x][x] = 1.0;
+            matrix.values[x][x] = T::one();
         }
-        return matrix;
+        matrix
     }
     /// Returns the number of rows.
     ///
@@ -91,14 +110,52 @@ impl Matrix {
         self.columns
     }
 
-    /// Returns true if the rows of the Matrix are different lengths.
+    /// Returns true if the rows of the Matrix are different lengths. A Matrix with no rows is not mangled.
     pub fn is_mangled(&self) -> bool {
-        let longest = self.values[0].len();
-        !self.values.iter().all(|row| row.len() == longest)
+        let first = match self.values.first() {
+            Some(row) => row.len(),
+            None => return false,
+        };
+        !self.values.iter().all(|row| row.len() == first)
+    }
+
+    /// Takes a tuple (usize, usize) and attempts swap rows in the Matrix. Will panic if index is out of bounds.
+    pub fn swap_rows(&mut self, rows: (usize, usize)) {
+        self.values.swap(rows.0, rows.1);
+    }
+
+    /// Takes a tuple (usize, usize) and attempts swap columns in the Matrix. Will panic if index is out of bounds.
+    pub fn swap_columns(&mut self, columns: (usize, usize)) {
+        for row in &mut self.values {
+            row.swap(columns.0, columns.1);
+        }
+    }
+    ///Appends a row to the Matrix
+    pub fn new_row(&mut self) {
+        self.values.push(vec![T::zero(); self.columns]);
+        self.rows += 1;
+    }
+}
+
+///The methods that need the order and magnitude of a Scalar.
+impl<T: Scalar> Matrix<T> {
+    /// Returns the 1-norm of the Matrix, the largest sum of absolute values in any column.
+    pub fn norm_one(&self) -> T {
+        let mut largest_sum = T::zero();
+        for column in 0..self.columns() {
+            let mut sum = T::zero();
+            for row in 0..self.rows() {
+                sum += self.values[row][column].abs();
+            }
+            if sum > largest_sum {
+                largest_sum = sum;
+            }
+        }
+        largest_sum
     }
 
     /// Returns the maximum value in a column and its location in a tuple (max (row, column)).
-    pub fn column_abs_max(&self, column: usize) -> (f32, (usize, usize)) {
+    pub fn column_abs_max(&self, column: usize) -> (T, (usize, usize)) {
         let mut row_of_largest: usize = 0;
         //Search specified column for index of largest.
         for row in 0..self.rows() {
@@ -114,7 +171,7 @@ impl Matrix {
     }
 
     /// Returns the maximum value in a row and its location in a tuple (max (row, column)).
-    pub fn row_abs_max(&self, row: usize) -> (f32, (usize, usize)) {
+    pub fn row_abs_max(&self, row: usize) -> (T, (usize, usize)) {
         let mut col_of_largest: usize = 0;
         let mut largest = self.values[row][col_of_largest].abs();
         //Search specified column for index of largest.
@@ -129,38 +186,16 @@ impl Matrix {
         (largest, (row, col_of_largest))
     }
 
-    /// Takes a tuple (usize, usize) and attempts swap rows in the Matrix. Will panic if index is out of bounds.
-    pub fn swap_rows(&mut self, rows: (usize, usize)) {
-        for col in 0..self.columns() {
-            let temp = self.values[rows.0][col];
-            self.values[rows.0][col] = self.values[rows.1][col];
-            self.values[rows.1][col] = temp;
-        }
-    }
-
     ///Multiply all elements in a row by some scalar value.
-    pub fn scale_row(&mut self, row: usize, scale: f32) {
+    pub fn scale_row(&mut self, row: usize, scale: T) {
         for element in &mut self.values[row][0..] {
-            *element *= scale;
+            *element *= scale.clone();
         }
     }
     /// Add some addend to all elements in a row.
-    pub fn add_to_row(&mut self, row: usize, addend: f32) {
+    pub fn add_to_row(&mut self, row: usize, addend: T) {
         for element in &mut self.values[row][0..] {
-            *element += addend;
+            *element += addend.clone();
         }
     }
-    /// Takes a tuple (usize, usize) and attempts swap columns in the Matrix. Will panic if index is out of bounds.
-    pub fn swap_columns(&mut self, columns: (usize, usize)) {
-        for row in &mut self.values {
-            let temp = row[columns.0];
-            row[columns.0] = row[columns.1];
-            row[columns.1] = temp;
-        }
-    }
-    ///Appends a row to the Matrix
-    pub fn new_row(&mut self) {
-        self.values.push(vec![0.0; self.columns.into()]);
-        self.rows = self.rows + 1;
-    }
 }
@@ -0,0 +1,152 @@
+use crate::{Field, Matrix, Scalar};
+
+///How an elimination chooses each pivot from the candidates left in the matrix.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pivoting {
+    ///Takes the candidate on the diagonal. Safe for diagonally dominant and symmetric positive definite matrices.
+    None,
+    ///Takes the largest candidate in the pivot column, by absolute value.
+    Partial,
+    ///Takes the largest candidate in the pivot column, relative to the largest element of its row in A.
+    ScaledPartial,
+    ///Takes the largest candidate anywhere in the remaining submatrix.
+    Complete,
+    ///Takes a candidate that is the largest in both its row and its column,
+    /// found by searching the columns and rows of the remaining submatrix in turn.
+    Rook,
+    ///Takes the first non-zero candidate in the pivot column. Any non-zero pivot will do when arithmetic is exact,
+    /// and the first keeps the rows in order. Exact types, such as Rational, pivot this way whatever the strategy.
+    FirstNonZero,
+}
+
+///Where each pivot was found, before it was swapped into place.
+/// The pivot of step `k` was found at (rows[k], columns[k]).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Pivots {
+    pub rows: Vec<usize>,
+    pub columns: Vec<usize>,
+}
+
+///Returns the location (row, column) of the next pivot among the candidate `rows` and `columns`.
+///Returns None when the pivot it would choose is zero.
+///### Arguments
+/// * `a`: the matrix as it stands at this step.
+/// * `rows`: the rows not yet pivoted, in order. The first is on the diagonal.
+/// * `columns`: the columns not yet pivoted, in order. The first is the pivot column of the column-wise strategies.
+/// * `pivoting`: the strategy.
+/// * `scales`: the reciprocal of the largest absolute value in each row of A, indexed by row.
+///   Only read by Pivoting::ScaledPartial.
+///
+/// For an exact type, the column-wise strategies take the first non-zero candidate in the first column, and
+/// complete and rook pivoting the first non-zero candidate of the submatrix, searching the columns in order.
+pub fn select_pivot<T: Scalar>(
+    a: &Matrix<T>,
+    rows: &[usize],
+    columns: &[usize],
+    pivoting: Pivoting,
+    scales: &[T],
+) -> Option<(usize, usize)> {
+    let (first_row, first_column) = (*rows.first()?, *columns.first()?);
+    if T::EXACT {
+        return match pivoting {
+            Pivoting::Complete | Pivoting::Rook => first_non_zero(a, rows, columns),
+            _ => first_non_zero(a, rows, &[first_column]),
+        };
+    }
+    let location = match pivoting {
+        Pivoting::None => (first_row, first_column),
+        Pivoting::Partial => (largest_in_column(a, rows, first_column, None), first_column),
+        Pivoting::ScaledPartial => (
+            largest_in_column(a, rows, first_column, Some(scales)),
+            first_column,
+        ),
+        Pivoting::Complete => {
+            let mut location = (first_row, first_column);
+            let mut max_absolute_value = T::zero();
+            '_traverse_submatrix: for &row in rows {
+                for &column in columns {
+                    if a.values[row][column].abs() <= max_absolute_value {
+                        continue;
+                    }
+                    max_absolute_value = a.values[row][column].abs();
+                    location = (row, column);
+                }
+            }
+            location
+        }
+        Pivoting::Rook => {
+            //Each move finds a strictly larger candidate, so the search ends.
+            let mut column = first_column;
+            let mut row = largest_in_column(a, rows, column, None);
+            '_search_rows_and_columns: loop {
+                let column_of_max = largest_in_row(a, row, columns);
+                if a.values[row][column_of_max].abs() <= a.values[row][column].abs() {
+                    break;
+                }
+                column = column_of_max;
+                let row_of_max = largest_in_column(a, rows, column, None);
+                if a.values[row_of_max][column].abs() <= a.values[row][column].abs() {
+                    break;
+                }
+                row = row_of_max;
+            }
+            (row, column)
+        }
+        Pivoting::FirstNonZero => {
+            first_non_zero(a, rows, &[first_column]).unwrap_or((first_row, first_column))
+        }
+    };
+    if a.values[location.0][location.1] == T::zero() {
+        return None;
+    }
+    Some(location)
+}
+
+///Returns the location of the first non-zero candidate, searching each of `columns` in turn, down `rows`.
+pub(crate) fn first_non_zero<T: Field>(
+    a: &Matrix<T>,
+    rows: &[usize],
+    columns: &[usize],
+) -> Option<(usize, usize)> {
+    let zero = T::zero();
+    '_traverse_columns: for &column in columns {
+        if let Some(&row) = rows.iter().find(|row| a.values[**row][column] != zero) {
+            return Some((row, column));
+        }
+    }
+    None
+}
+
+///Returns the row holding the largest candidate in `column`, weighted by `scales` if given.
+/// Ties go to the earliest row.
+fn largest_in_column<T: Scalar>(
+    a: &Matrix<T>,
+    rows: &[usize],
+    column: usize,
+    scales: Option<&[T]>,
+) -> usize {
+    let weighted = |row: usize| match scales {
+        Some(scales) => scales[row].clone() * a.values[row][column].abs(),
+        None => a.values[row][column].abs(),
+    };
+    let mut row_of_max = rows[0];
+    let mut max = weighted(row_of_max);
+    for &row in &rows[1..] {
+        let candidate = weighted(row);
+        if candidate > max {
+            (max, row_of_max) = (candidate, row);
+        }
+    }
+    row_of_max
+}
+
+///Returns the column holding the largest candidate in `row`. Ties go to the earliest column.
+fn largest_in_row<T: Scalar>(a: &Matrix<T>, row: usize, columns: &[usize]) -> usize {
+    let mut column_of_max = columns[0];
+    for &column in &columns[1..] {
+        if a.values[row][column].abs() > a.values[row][column_of_max].abs() {
+            column_of_max = column;
+        }
+    }
+    column_of_max
+}
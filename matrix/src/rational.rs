@@ -0,0 +1,282 @@
+//!Exact rational numbers, an element type for which elimination makes no rounding error.
+use crate::bigint::BigInt;
+use crate::{Field, Scalar};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+///A fraction of arbitrary precision integers, kept in lowest terms with a positive denominator,
+/// so that equal values are stored alike.
+///
+///### Usage
+/// Elimination over Rational is exact, so inverses and determinants come out as fractions.
+/// The integers grow with the size of the matrix, and so does the cost of each operation.
+/// ```
+///    use matrix::{rational::Rational, Scalar};
+///    let third = Rational::new(1, 3);
+///    assert_eq!(third.clone() + third.clone() + third, Rational::from(1));
+///    assert_eq!(Rational::new(6, -4).to_string(), "-3/2");
+///    assert_eq!(Rational::from_f64(0.375), Rational::new(3, 8));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: BigInt,
+    denominator: BigInt,
+}
+
+impl Rational {
+    ///Returns `numerator` / `denominator` in lowest terms. Panics if `denominator` is zero.
+    pub fn new(numerator: i64, denominator: i64) -> Self {
+        Rational::from_parts(BigInt::from(numerator), BigInt::from(denominator))
+    }
+
+    ///Returns `numerator` / `denominator` in lowest terms. Panics if `denominator` is zero.
+    pub fn from_parts(numerator: BigInt, denominator: BigInt) -> Self {
+        assert!(!denominator.is_zero(), "Division by zero.");
+        let divisor = numerator.gcd(&denominator);
+        let (mut numerator, mut denominator) = (
+            numerator.div_rem(&divisor).0,
+            denominator.div_rem(&divisor).0,
+        );
+        if denominator.is_negative() {
+            (numerator, denominator) = (-numerator, -denominator);
+        }
+        Rational {
+            numerator,
+            denominator,
+        }
+    }
+
+    pub fn numerator(&self) -> &BigInt {
+        &self.numerator
+    }
+
+    ///Always positive.
+    pub fn denominator(&self) -> &BigInt {
+        &self.denominator
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == BigInt::from(1)
+    }
+}
+
+impl Default for Rational {
+    fn default() -> Self {
+        Rational::from(0)
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Self {
+        Rational {
+            numerator: BigInt::from(value),
+            denominator: BigInt::from(1),
+        }
+    }
+}
+
+impl From<BigInt> for Rational {
+    fn from(value: BigInt) -> Self {
+        Rational {
+            numerator: value,
+            denominator: BigInt::from(1),
+        }
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        //The denominators are positive, so cross multiplying keeps the order.
+        let left = self.numerator.clone() * other.denominator.clone();
+        let right = other.numerator.clone() * self.denominator.clone();
+        left.cmp(&right)
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+    fn neg(self) -> Rational {
+        Rational {
+            numerator: -self.numerator,
+            denominator: self.denominator,
+        }
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+    fn add(self, other: Rational) -> Rational {
+        if self.denominator == other.denominator {
+            return Rational::from_parts(self.numerator + other.numerator, self.denominator);
+        }
+        let numerator =
+            self.numerator * other.denominator.clone() + other.numerator * self.denominator.clone();
+        Rational::from_parts(numerator, self.denominator * other.denominator)
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+    fn sub(self, other: Rational) -> Rational {
+        self + -other
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+    fn mul(self, other: Rational) -> Rational {
+        if self.numerator.is_zero() || other.numerator.is_zero() {
+            return Rational::default();
+        }
+        Rational::from_parts(
+            self.numerator * other.numerator,
+            self.denominator * other.denominator,
+        )
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+    ///Panics if `other` is zero.
+    fn div(self, other: Rational) -> Rational {
+        Rational::from_parts(
+            self.numerator * other.denominator,
+            self.denominator * other.numerator,
+        )
+    }
+}
+
+impl AddAssign for Rational {
+    fn add_assign(&mut self, other: Rational) {
+        *self = std::mem::take(self) + other;
+    }
+}
+
+impl SubAssign for Rational {
+    fn sub_assign(&mut self, other: Rational) {
+        *self = std::mem::take(self) - other;
+    }
+}
+
+impl MulAssign for Rational {
+    fn mul_assign(&mut self, other: Rational) {
+        *self = std::mem::take(self) * other;
+    }
+}
+
+impl DivAssign for Rational {
+    fn div_assign(&mut self, other: Rational) {
+        *self = std::mem::take(self) / other;
+    }
+}
+
+impl fmt::Display for Rational {
+    ///Writes "numerator/denominator", or just the numerator of an integer.
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self.is_integer() {
+            true => self.numerator.to_string(),
+            false => format!("{}/{}", self.numerator, self.denominator),
+        };
+        formatter.pad(&text)
+    }
+}
+
+impl Field for Rational {
+    fn zero() -> Self {
+        Rational::from(0)
+    }
+    fn one() -> Self {
+        Rational::from(1)
+    }
+}
+
+impl Scalar for Rational {
+    const EXACT: bool = true;
+
+    fn abs(&self) -> Self {
+        Rational {
+            numerator: self.numerator.abs(),
+            denominator: self.denominator.clone(),
+        }
+    }
+    fn is_finite(&self) -> bool {
+        true
+    }
+    ///Exact: every finite f64 is a fraction with a power of two denominator. Panics on NaN and the infinities.
+    fn from_f64(value: f64) -> Self {
+        assert!(value.is_finite(), "{} is not a rational number.", value);
+        if value == 0.0 {
+            return Rational::default();
+        }
+        let bits = value.abs().to_bits();
+        let exponent = ((bits >> 52) & 0x7ff) as i64;
+        //Subnormals have no implicit leading bit, and the exponent of the smallest normal.
+        let (mantissa, exponent) = match exponent {
+            0 => (bits & ((1 << 52) - 1), -1074),
+            _ => ((bits & ((1 << 52) - 1)) | (1 << 52), exponent - 1075),
+        };
+        let mut numerator = BigInt::from(mantissa);
+        if value < 0.0 {
+            numerator = -numerator;
+        }
+        match exponent >= 0 {
+            true => Rational::from(numerator.shl(exponent as usize)),
+            false => Rational::from_parts(numerator, BigInt::from(1).shl(-exponent as usize)),
+        }
+    }
+    ///The nearest f64, near enough. Numerators and denominators too large for f64 are shifted down first,
+    /// and the quotient scaled back by the difference of the shifts.
+    fn to_f64(&self) -> f64 {
+        let excess = |value: &BigInt| value.bits().saturating_sub(1000);
+        let (numerator_shift, denominator_shift) =
+            (excess(&self.numerator), excess(&self.denominator));
+        let quotient = self.numerator.shr(numerator_shift).to_f64()
+            / self.denominator.shr(denominator_shift).to_f64();
+        quotient * 2f64.powi(numerator_shift as i32 - denominator_shift as i32)
+    }
+    ///Zero. Arithmetic is exact, so there is no rounding error to measure.
+    fn epsilon() -> Self {
+        Rational::from(0)
+    }
+    ///Exact when the numerator and denominator are both perfect squares. Otherwise the square root of to_f64(),
+    /// rounded like any f64, so that least squares in Rational is only as exact as its square roots.
+    /// Panics on a negative value.
+    fn sqrt(&self) -> Self {
+        assert!(
+            !self.numerator.is_negative(),
+            "{} has no rational square root.",
+            self
+        );
+        let (numerator, denominator) = (
+            integer_sqrt(&self.numerator),
+            integer_sqrt(&self.denominator),
+        );
+        let square = |root: &BigInt| root.clone() * root.clone();
+        if square(&numerator) == self.numerator && square(&denominator) == self.denominator {
+            return Rational::from_parts(numerator, denominator);
+        }
+        Rational::from_f64(self.to_f64().sqrt())
+    }
+}
+
+///The largest integer whose square is at most `value`, by Newton's method from above. `value` is not negative.
+fn integer_sqrt(value: &BigInt) -> BigInt {
+    if value.is_zero() {
+        return BigInt::zero();
+    }
+    let mut root = BigInt::from(1).shl(value.bits().div_ceil(2));
+    loop {
+        let next = (root.clone() + value.div_rem(&root).0).shr(1);
+        if next >= root {
+            return root;
+        }
+        root = next;
+    }
+}
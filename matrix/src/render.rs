@@ -0,0 +1,258 @@
+use crate::trace::{Frame, Step};
+use crate::{Matrix, Scalar};
+
+///Renders recorded frames as a LaTeX worked example.
+///
+///### Usage
+/// Each step becomes a display equation, the matrices before and after it in bmatrix environments,
+/// joined by an arrow labelled with the row operation. The current pivot is boxed.
+/// Rows and columns are numbered from 1, as they are on paper.
+/// ```
+///    use matrix::render;
+///    use matrix::trace::{Recorder, Step, Trace};
+///    let mut a = matrix::Matrix::from(vec![vec![1.0, 2.0], vec![2.0, 1.0]]);
+///    let mut recorder = Recorder::new();
+///    recorder.start(&a, None);
+///    a.values[1] = vec![0.0, -3.0];
+///    recorder.record(Step::EliminateRow { target: 1, source: 0, factor: 2.0 }, &a, None);
+///    assert!(render::latex(recorder.frames()).contains(r"\xrightarrow{R_{2} \leftarrow R_{2} - 2 R_{1}}"));
+/// ```
+pub fn latex<T: Scalar>(frames: &[Frame<T>]) -> String {
+    let mut pivot = None;
+    let mut document = String::new();
+    for (number, frame) in frames.iter().enumerate() {
+        let pivot_before = pivot;
+        pivot = track_pivot(pivot, &frame.step);
+
+        document += &format!("% Step {}\n\\[\n", number + 1);
+        document += &latex_snapshot(&frame.before.a, frame.before.b.as_ref(), pivot_before);
+        document += &format!("\n\\xrightarrow{{{}}}\n", latex_annotation(&frame.step));
+        document += &latex_snapshot(&frame.after.a, frame.after.b.as_ref(), pivot);
+        document += "\n\\]\n\n";
+    }
+    document
+}
+
+///Renders recorded frames as a GitHub Markdown worked example.
+///
+///### Usage
+/// Each step becomes a heading naming the row operation, followed by tables of the matrices before and after it.
+/// Columns of B are labelled b1, b2... The current pivot is in bold.
+/// Rows and columns are numbered from 1, as they are on paper.
+pub fn markdown<T: Scalar>(frames: &[Frame<T>]) -> String {
+    let mut pivot = None;
+    let mut document = String::new();
+    for (number, frame) in frames.iter().enumerate() {
+        let pivot_before = pivot;
+        pivot = track_pivot(pivot, &frame.step);
+
+        document += &format!(
+            "### Step {}: {}\n\n",
+            number + 1,
+            markdown_annotation(&frame.step)
+        );
+        document += "Before:\n\n";
+        document += &markdown_table(&frame.before.a, frame.before.b.as_ref(), pivot_before);
+        document += "\nAfter:\n\n";
+        document += &markdown_table(&frame.after.a, frame.after.b.as_ref(), pivot);
+        document += "\n";
+    }
+    document
+}
+
+///Follows the pivot through the swaps that move it. Returns the pivot location after `step`.
+fn track_pivot<T>(pivot: Option<(usize, usize)>, step: &Step<T>) -> Option<(usize, usize)> {
+    let swapped = |index: usize, (first, second): (usize, usize)| {
+        if index == first {
+            second
+        } else if index == second {
+            first
+        } else {
+            index
+        }
+    };
+    match (step, pivot) {
+        (Step::SelectPivot { row, column }, _) => Some((*row, *column)),
+        (Step::SwapRows { rows }, Some((row, column))) => Some((swapped(row, *rows), column)),
+        (Step::SwapColumns { columns }, Some((row, column))) => {
+            Some((row, swapped(column, *columns)))
+        }
+        (_, pivot) => pivot,
+    }
+}
+
+fn latex_annotation<T: Scalar>(step: &Step<T>) -> String {
+    match step {
+        Step::SelectPivot { row, column } => {
+            format!("\\text{{pivot }} a_{{{},{}}}", row + 1, column + 1)
+        }
+        Step::SwapRows { rows } => {
+            format!("R_{{{}}} \\leftrightarrow R_{{{}}}", rows.0 + 1, rows.1 + 1)
+        }
+        Step::SwapElements { rows, column } => format!(
+            "b_{{{},{}}} \\leftrightarrow b_{{{},{}}}",
+            rows.0 + 1,
+            column + 1,
+            rows.1 + 1,
+            column + 1
+        ),
+        Step::SwapColumns { columns } => {
+            format!(
+                "C_{{{}}} \\leftrightarrow C_{{{}}}",
+                columns.0 + 1,
+                columns.1 + 1
+            )
+        }
+        Step::ScaleRow { row, scale } => {
+            format!(
+                "R_{{{}}} \\leftarrow {} R_{{{}}}",
+                row + 1,
+                number(scale),
+                row + 1
+            )
+        }
+        Step::ScaleColumn { column, scale } => format!(
+            "C_{{{}}} \\leftarrow {} C_{{{}}} \\text{{ below the diagonal}}",
+            column + 1,
+            number(scale),
+            column + 1
+        ),
+        Step::EliminateRow {
+            target,
+            source,
+            factor,
+        } => {
+            let sign = if *factor < T::zero() { "+" } else { "-" };
+            format!(
+                "R_{{{}}} \\leftarrow R_{{{}}} {} {} R_{{{}}}",
+                target + 1,
+                target + 1,
+                sign,
+                number(&factor.abs()),
+                source + 1
+            )
+        }
+        Step::Substitute { row, column } => {
+            format!("\\text{{substitute }} ({}, {})", row + 1, column + 1)
+        }
+    }
+}
+
+fn markdown_annotation<T: Scalar>(step: &Step<T>) -> String {
+    match step {
+        Step::SelectPivot { row, column } => format!("pivot at ({}, {})", row + 1, column + 1),
+        Step::SwapRows { rows } => format!("R{} ↔ R{}", rows.0 + 1, rows.1 + 1),
+        Step::SwapElements { rows, column } => format!(
+            "b({}, {}) ↔ b({}, {})",
+            rows.0 + 1,
+            column + 1,
+            rows.1 + 1,
+            column + 1
+        ),
+        Step::SwapColumns { columns } => format!("C{} ↔ C{}", columns.0 + 1, columns.1 + 1),
+        Step::ScaleRow { row, scale } => {
+            format!("R{} ← {}R{}", row + 1, number(scale), row + 1)
+        }
+        Step::ScaleColumn { column, scale } => format!(
+            "C{} ← {}C{} below the diagonal",
+            column + 1,
+            number(scale),
+            column + 1
+        ),
+        Step::EliminateRow {
+            target,
+            source,
+            factor,
+        } => {
+            let sign = if *factor < T::zero() { "+" } else { "−" };
+            format!(
+                "R{} ← R{} {} {}R{}",
+                target + 1,
+                target + 1,
+                sign,
+                number(&factor.abs()),
+                source + 1
+            )
+        }
+        Step::Substitute { row, column } => format!("substitute ({}, {})", row + 1, column + 1),
+    }
+}
+
+fn latex_snapshot<T: Scalar>(
+    a: &Matrix<T>,
+    b: Option<&Matrix<T>>,
+    pivot: Option<(usize, usize)>,
+) -> String {
+    let mut snapshot = latex_bmatrix(a, pivot);
+    if let Some(b) = b {
+        snapshot += " \\;\\Big|\\; ";
+        snapshot += &latex_bmatrix(b, None);
+    }
+    snapshot
+}
+
+fn latex_bmatrix<T: Scalar>(matrix: &Matrix<T>, pivot: Option<(usize, usize)>) -> String {
+    let rows: Vec<String> = (0..matrix.rows())
+        .map(|row| {
+            let elements: Vec<String> = (0..matrix.values[row].len())
+                .map(|column| {
+                    let element = number(&matrix.values[row][column]);
+                    if pivot == Some((row, column)) {
+                        format!("\\boxed{{{}}}", element)
+                    } else {
+                        element
+                    }
+                })
+                .collect();
+            elements.join(" & ")
+        })
+        .collect();
+    format!(
+        "\\begin{{bmatrix}} {} \\end{{bmatrix}}",
+        rows.join(" \\\\ ")
+    )
+}
+
+fn markdown_table<T: Scalar>(
+    a: &Matrix<T>,
+    b: Option<&Matrix<T>>,
+    pivot: Option<(usize, usize)>,
+) -> String {
+    let b_columns = b.map_or(0, |b| b.columns());
+    let mut header = vec![String::new()];
+    header.extend((0..a.columns()).map(|column| format!("C{}", column + 1)));
+    header.extend((0..b_columns).map(|column| format!("b{}", column + 1)));
+
+    let mut table = format!("| {} |\n", header.join(" | "));
+    table += &format!("|{}\n", "---|".repeat(header.len()));
+    for row in 0..a.rows() {
+        let mut cells = vec![format!("R{}", row + 1)];
+        for column in 0..a.values[row].len() {
+            let element = number(&a.values[row][column]);
+            if pivot == Some((row, column)) {
+                cells.push(format!("**{}**", element));
+            } else {
+                cells.push(element);
+            }
+        }
+        if let Some(b) = b {
+            cells.extend(b.values[row].iter().map(number));
+        }
+        table += &format!("| {} |\n", cells.join(" | "));
+    }
+    table
+}
+
+///Formats a number for reading: integers without a decimal point, everything else to at most 4 decimal places.
+fn number<T: Scalar>(value: &T) -> String {
+    let value = value.to_f64();
+    if value == value.trunc() && value.abs() < 1.0e7 {
+        //Avoids printing -0.
+        return format!("{}", value as i64);
+    }
+    let formatted = format!("{:.4}", value);
+    match formatted.trim_end_matches('0').trim_end_matches('.') {
+        "-0" => "0".to_string(),
+        trimmed => trimmed.to_string(),
+    }
+}
@@ -0,0 +1,133 @@
+use crate::kernels::{f32_kernels, f64_kernels, plain};
+use crate::Field;
+
+///The element type of the solvers. Implemented for f32, f64, Rational, Interval and Dual.
+///
+/// The arithmetic, zero and one come from Field. Scalar adds the order and magnitude that pivoting and norms need.
+pub trait Scalar: Field + PartialOrd {
+    ///True for types whose arithmetic does not round, such as Rational. Pivots of exact types are chosen
+    /// as the first non-zero candidate, since there is no rounding error for a large pivot to limit. See Pivoting::FirstNonZero.
+    const EXACT: bool = false;
+
+    ///The absolute value. Used to compare candidate pivots.
+    fn abs(&self) -> Self;
+
+    ///Returns false for NaN and the infinities.
+    fn is_finite(&self) -> bool;
+
+    ///Converts from f64, rounding if necessary.
+    fn from_f64(value: f64) -> Self;
+
+    ///Converts to f64, rounding if necessary.
+    fn to_f64(&self) -> f64;
+
+    ///The difference between 1 and the next larger representable value, the unit of relative rounding error.
+    fn epsilon() -> Self;
+
+    ///The square root. Has no default, so that each type decides how it rounds, and what it counts.
+    fn sqrt(&self) -> Self;
+
+    ///The vectorized form of kernels::scale. Defaults to the plain loop.
+    fn vectorized_scale(values: &mut [Self], factor: &Self) {
+        plain::scale(values, factor);
+    }
+
+    ///The vectorized form of kernels::add. Defaults to the plain loop.
+    fn vectorized_add(values: &mut [Self], addend: &Self) {
+        plain::add(values, addend);
+    }
+
+    ///The vectorized form of kernels::subtract_multiple. Defaults to the plain loop.
+    fn vectorized_subtract_multiple(target: &mut [Self], factor: &Self, source: &[Self]) {
+        plain::subtract_multiple(target, factor, source);
+    }
+
+    ///The vectorized form of kernels::dot_subtract. Defaults to the plain loop.
+    fn vectorized_dot_subtract(initial: Self, x: &[Self], y: &[Self]) -> Self {
+        plain::dot_subtract(initial, x, y)
+    }
+}
+
+impl Field for f32 {
+    fn zero() -> Self {
+        0.0
+    }
+    fn one() -> Self {
+        1.0
+    }
+}
+
+impl Scalar for f32 {
+    fn abs(&self) -> Self {
+        f32::abs(*self)
+    }
+    fn is_finite(&self) -> bool {
+        f32::is_finite(*self)
+    }
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+    fn to_f64(&self) -> f64 {
+        *self as f64
+    }
+    fn epsilon() -> Self {
+        f32::EPSILON
+    }
+    fn sqrt(&self) -> Self {
+        f32::sqrt(*self)
+    }
+    fn vectorized_scale(values: &mut [Self], factor: &Self) {
+        f32_kernels::scale(values, *factor);
+    }
+    fn vectorized_add(values: &mut [Self], addend: &Self) {
+        f32_kernels::add(values, *addend);
+    }
+    fn vectorized_subtract_multiple(target: &mut [Self], factor: &Self, source: &[Self]) {
+        f32_kernels::subtract_multiple(target, *factor, source);
+    }
+    fn vectorized_dot_subtract(initial: Self, x: &[Self], y: &[Self]) -> Self {
+        f32_kernels::dot_subtract(initial, x, y)
+    }
+}
+
+impl Field for f64 {
+    fn zero() -> Self {
+        0.0
+    }
+    fn one() -> Self {
+        1.0
+    }
+}
+
+impl Scalar for f64 {
+    fn abs(&self) -> Self {
+        f64::abs(*self)
+    }
+    fn is_finite(&self) -> bool {
+        f64::is_finite(*self)
+    }
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+    fn to_f64(&self) -> f64 {
+        *self
+    }
+    fn epsilon() -> Self {
+        f64::EPSILON
+    }
+    fn sqrt(&self) -> Self {
+        f64::sqrt(*self)
+    }
+    fn vectorized_scale(values: &mut [Self], factor: &Self) {
+        f64_kernels::scale(values, *factor);
+    }
+    fn vectorized_add(values: &mut [Self], addend: &Self) {
+        f64_kernels::add(values, *addend);
+    }
+    fn vectorized_subtract_multiple(target: &mut [Self], factor: &Self, source: &[Self]) {
+        f64_kernels::subtract_multiple(target, *factor, source);
+    }
+    fn vectorized_dot_subtract(initial: Self, x: &[Self], y: &[Self]) -> Self {
+        f64_kernels::dot_subtract(initial, x, y)
+    }
+}
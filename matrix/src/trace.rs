@@ -0,0 +1,133 @@
+use crate::{Matrix, Scalar};
+
+///A single step taken by an elimination algorithm, such as gauss_jordan, decompose or crout.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Step<T = f32> {
+    ///The element at (row, column) was chosen as the pivot.
+    SelectPivot { row: usize, column: usize },
+    ///Two rows were swapped in A, and in B when there is one.
+    SwapRows { rows: (usize, usize) },
+    ///Two elements in the same column of B were swapped. Crout's algorithm permutes one column at a time.
+    SwapElements { rows: (usize, usize), column: usize },
+    ///Two columns of A were swapped.
+    SwapColumns { columns: (usize, usize) },
+    ///A row was multiplied by `scale` in A, and in B when there is one.
+    ScaleRow { row: usize, scale: T },
+    ///The elements below the diagonal in `column` of A were multiplied by `scale`.
+    ScaleColumn { column: usize, scale: T },
+    ///`factor` times the `source` row was subtracted from the `target` row, in A and in B when there is one.
+    EliminateRow {
+        target: usize,
+        source: usize,
+        factor: T,
+    },
+    ///The element at (row, column) had the dot product of previously computed elements subtracted from it,
+    ///and was divided by the pivot where the algorithm calls for it.
+    Substitute { row: usize, column: usize },
+}
+
+///Observes the steps taken by an elimination algorithm.
+///
+///### Usage
+/// Pass NoTrace when nothing should be observed, or Recorder to keep every step.
+/// ```
+///    use matrix::trace::{Recorder, Step, Trace};
+///    use matrix::Matrix;
+///    let mut recorder = Recorder::new();
+///    let mut a: Matrix = Matrix::identity(2);
+///    recorder.start(&a, None);
+///    a.swap_rows((0, 1));
+///    recorder.record(Step::SwapRows { rows: (0, 1) }, &a, None);
+///    assert_eq!(recorder.frames()[0].before.a, Matrix::identity(2));
+///    assert_eq!(recorder.frames()[0].after.a, a);
+/// ```
+pub trait Trace<T = f32> {
+    ///Called once before the first step, with the matrices as they were given to the algorithm.
+    fn start(&mut self, _a: &Matrix<T>, _b: Option<&Matrix<T>>) {}
+
+    ///Called after every step, with the matrices as they stand after the step.
+    fn record(&mut self, step: Step<T>, a: &Matrix<T>, b: Option<&Matrix<T>>);
+}
+
+///Observes nothing. The default for every algorithm.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NoTrace;
+
+impl<T> Trace<T> for NoTrace {
+    fn record(&mut self, _step: Step<T>, _a: &Matrix<T>, _b: Option<&Matrix<T>>) {}
+}
+
+///A copy of the matrices an algorithm is working on.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Snapshot<T = f32> {
+    pub a: Matrix<T>,
+    pub b: Option<Matrix<T>>,
+}
+
+///A step, with the matrices as they stood before and after it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame<T = f32> {
+    pub step: Step<T>,
+    pub before: Snapshot<T>,
+    pub after: Snapshot<T>,
+}
+
+///Records every step, with snapshots, so an algorithm can be replayed one step at a time.
+///
+/// Every step copies the matrices twice. Intended for the small matrices used when learning an algorithm.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recorder<T = f32> {
+    current: Option<Snapshot<T>>,
+    frames: Vec<Frame<T>>,
+}
+
+impl<T: Scalar> Recorder<T> {
+    pub fn new() -> Self {
+        Recorder {
+            current: None,
+            frames: Vec::new(),
+        }
+    }
+
+    ///Returns the recorded steps in the order they were taken.
+    pub fn frames(&self) -> &[Frame<T>] {
+        &self.frames
+    }
+
+    ///Returns the recorded steps without their snapshots.
+    pub fn steps(&self) -> Vec<Step<T>> {
+        self.frames.iter().map(|frame| frame.step.clone()).collect()
+    }
+}
+
+impl<T: Scalar> Default for Recorder<T> {
+    fn default() -> Self {
+        Recorder::new()
+    }
+}
+
+impl<T: Scalar> Trace<T> for Recorder<T> {
+    fn start(&mut self, a: &Matrix<T>, b: Option<&Matrix<T>>) {
+        self.current = Some(Snapshot {
+            a: a.clone(),
+            b: b.cloned(),
+        });
+    }
+
+    fn record(&mut self, step: Step<T>, a: &Matrix<T>, b: Option<&Matrix<T>>) {
+        let after = Snapshot {
+            a: a.clone(),
+            b: b.cloned(),
+        };
+        //Without a call to start(), the first step has nothing to compare against.
+        let before = self
+            .current
+            .replace(after.clone())
+            .unwrap_or_else(|| after.clone());
+        self.frames.push(Frame {
+            step,
+            before,
+            after,
+        });
+    }
+}
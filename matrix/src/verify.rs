@@ -0,0 +1,117 @@
+use crate::{Matrix, Scalar};
+
+///How well a computed solution x satisfies A * x = b.
+///
+/// Every measure is taken column by column of b, and the worst column is reported.
+/// Norms are 1-norms, to match the condition estimate of lu_decomposition::reciprocal_condition.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Verification<T = f32> {
+    ///r = b - A * x, with the shape of b.
+    pub residual: Matrix<T>,
+    ///Rigal-Gaches: the smallest e such that (A + dA) * x = b + db with ||dA|| <= e * ||A|| and ||db|| <= e * ||b||.
+    pub normwise_backward_error: T,
+    ///Oettli-Prager: the smallest e such that (A + dA) * x = b + db with |dA| <= e * |A| and |db| <= e * |b|, element by element.
+    pub componentwise_backward_error: T,
+    ///An upper bound on ||x - x_exact|| / ||x_exact||. None when no condition estimate was given,
+    ///or when the problem is too ill-conditioned for the bound to say anything.
+    pub forward_error_bound: Option<T>,
+}
+
+impl<T: Scalar> Verification<T> {
+    ///Returns true when the normwise and componentwise backward errors are both at most `tolerance`.
+    ///A backward stable solver gives backward errors of a small multiple of machine epsilon.
+    pub fn is_acceptable(&self, tolerance: T) -> bool {
+        self.normwise_backward_error <= tolerance && self.componentwise_backward_error <= tolerance
+    }
+}
+
+///Measures how well the computed solution `x` solves A * x = b.
+///### Arguments
+/// * `a`: the n x n matrix A, as it was before it was decomposed or reduced.
+/// * `x`: the computed n x m solution.
+/// * `b`: the n x m right hand side.
+/// * `reciprocal_condition`: an estimate of 1 / cond(A) in the 1-norm, if one is available. Needed for the forward error bound.
+///### Usage
+/// The forward error bound is 2 * cond(A) * e / (1 - cond(A) * e), where e is the normwise backward error.
+/// The componentwise backward error also catches errors in rows much smaller than the rest, which the normwise error hides.
+/// ```
+///    use matrix::verify::verify;
+///    let a = matrix::Matrix::from(vec![vec![3.0, -2.0], vec![6.0, 4.0]]);
+///    let x = matrix::Matrix::from(vec![vec![0.0], vec![-2.0]]);
+///    let b = matrix::Matrix::from(vec![vec![4.0], vec![-8.0]]);
+///    let verification = verify(&a, &x, &b, Some(1.0 / 3.75));
+///    assert!(verification.is_acceptable(1e-6));
+///    assert_eq!(verification.forward_error_bound, Some(0.0));
+/// ```
+pub fn verify<T: Scalar>(
+    a: &Matrix<T>,
+    x: &Matrix<T>,
+    b: &Matrix<T>,
+    reciprocal_condition: Option<T>,
+) -> Verification<T> {
+    assert_eq!(a.columns(), x.rows(), "x has the wrong number of rows");
+    assert_eq!(a.rows(), b.rows(), "b has the wrong number of rows");
+    assert_eq!(
+        x.columns(),
+        b.columns(),
+        "x and b have differing numbers of columns"
+    );
+    let a_norm = a.norm_one();
+    let mut residual = Matrix::new(b.rows(), b.columns());
+    let mut normwise_backward_error = T::zero();
+    let mut componentwise_backward_error = T::zero();
+
+    '_traverse_right_hand_sides: for column in 0..b.columns() {
+        let mut residual_norm = T::zero();
+        let mut x_norm = T::zero();
+        let mut b_norm = T::zero();
+        for row in 0..x.rows() {
+            x_norm += x.values[row][column].abs();
+        }
+        '_traverse_rows: for row in 0..a.rows() {
+            //r = b - A * x, alongside |A| * |x| + |b| for Oettli-Prager.
+            let mut r = b.values[row][column].clone();
+            let mut magnitude = b.values[row][column].abs();
+            for k in 0..a.columns() {
+                r -= a.values[row][k].clone() * x.values[k][column].clone();
+                magnitude += a.values[row][k].abs() * x.values[k][column].abs();
+            }
+            //|r| <= |A| * |x| + |b|, so a zero magnitude means a zero residual.
+            if magnitude != T::zero() {
+                let row_error = r.abs() / magnitude;
+                if row_error > componentwise_backward_error {
+                    componentwise_backward_error = row_error;
+                }
+            }
+            residual_norm += r.abs();
+            b_norm += b.values[row][column].abs();
+            residual.values[row][column] = r;
+        }
+        let denominator = a_norm.clone() * x_norm + b_norm;
+        if denominator != T::zero() {
+            let column_error = residual_norm / denominator;
+            if column_error > normwise_backward_error {
+                normwise_backward_error = column_error;
+            }
+        }
+    }
+
+    let forward_error_bound = reciprocal_condition.and_then(|reciprocal_condition| {
+        if reciprocal_condition <= T::zero() {
+            return None;
+        }
+        //cond(A) * e, where cond(A) = 1 / rcond.
+        let amplified = normwise_backward_error.clone() / reciprocal_condition;
+        if amplified >= T::one() {
+            return None;
+        }
+        Some(T::from_f64(2.0) * amplified.clone() / (T::one() - amplified))
+    });
+
+    Verification {
+        residual,
+        normwise_backward_error,
+        componentwise_backward_error,
+        forward_error_bound,
+    }
+}
@@ -0,0 +1,62 @@
+use matrix::bit_matrix::BitMatrix;
+use matrix::field::PrimeField;
+use matrix::{Field, Matrix};
+
+#[test]
+fn every_non_zero_element_has_an_inverse() {
+    type F13 = PrimeField<13>;
+    assert_eq!(F13::zero().inverse(), None);
+    for value in 1..13 {
+        let element = F13::new(value);
+        assert_eq!(element * element.inverse().unwrap(), F13::one());
+        assert_eq!(F13::one() / element * element, F13::one());
+    }
+    assert_eq!(F13::new(20), F13::new(7));
+    assert_eq!(F13::new(3) - F13::new(5), F13::new(11));
+    assert_eq!(F13::new(4).to_string(), "4");
+}
+
+#[test]
+fn large_primes_do_not_overflow() {
+    //2^61 - 1, a Mersenne prime.
+    type F = PrimeField<2305843009213693951>;
+    let large = F::new(2305843009213693950);
+    assert_eq!(large * large, F::one());
+    assert_eq!(large + large, F::new(2305843009213693949));
+    assert_eq!(F::one() / large, large);
+}
+
+#[test]
+#[should_panic(expected = "has no inverse modulo 12")]
+fn composite_moduli_are_found_on_division() {
+    let _ = PrimeField::<12>::new(1) / PrimeField::<12>::new(4);
+}
+
+#[test]
+fn prime_field_matrices() {
+    type F3 = PrimeField<3>;
+    let mut identity = Matrix::<F3>::identity(3);
+    identity.swap_rows((0, 2));
+    assert_eq!(identity.values[0], vec![F3::new(0), F3::new(0), F3::new(1)]);
+}
+
+#[test]
+fn bit_matrix_rows_span_several_words() {
+    let mut a = BitMatrix::new(3, 150);
+    for column in [0, 63, 64, 127, 128, 149] {
+        a.set(0, column, true);
+    }
+    a.set(1, 64, true);
+    a.add_row(0, 1);
+    assert!(!a.get(1, 64));
+    assert!(a.get(1, 149) && a.get(1, 63));
+    a.add_row(1, 0);
+    assert_eq!(a.row_words(0), &[0, 1, 0]);
+    a.swap_rows((0, 2));
+    assert!(a.get(2, 64));
+    a.add_row(2, 2);
+    assert_eq!(a.row(2), vec![0; 150]);
+    let augmented = BitMatrix::identity(3).augment(&a);
+    assert_eq!((augmented.rows(), augmented.columns()), (3, 153));
+    assert_eq!(augmented.columns_range(0, 3), BitMatrix::identity(3));
+}
@@ -0,0 +1,87 @@
+use matrix::interval::Interval;
+use matrix::rational::Rational;
+use matrix::Scalar;
+
+///Returns `length` values spread over many orders of magnitude, from a linear congruential generator.
+fn pseudo_random(length: usize, seed: u64) -> Vec<f64> {
+    let mut state = seed;
+    (0..length)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let mantissa = (state >> 11) as f64 / (1u64 << 53) as f64 - 0.5;
+            mantissa * 2f64.powi((state % 41) as i32 - 20)
+        })
+        .collect()
+}
+
+#[test]
+fn outward_rounding_encloses_the_exact_result() {
+    let (x, y) = (pseudo_random(200, 1), pseudo_random(200, 2));
+    let exact = |value: f64| Rational::from_f64(value);
+    for (x, y) in x.into_iter().zip(y) {
+        let (a, b) = (Interval::point(x), Interval::point(y));
+        let results = [
+            (a + b, exact(x) + exact(y)),
+            (a - b, exact(x) - exact(y)),
+            (a * b, exact(x) * exact(y)),
+            (a / b, exact(x) / exact(y)),
+        ];
+        for (interval, exact_result) in results {
+            assert!(exact(interval.lower()) <= exact_result);
+            assert!(exact_result <= exact(interval.upper()));
+            //Rounded outward by at most one unit in the last place.
+            assert!(interval.upper() <= interval.lower().next_up());
+        }
+    }
+}
+
+#[test]
+fn exact_operations_are_not_widened() {
+    let sum = Interval::point(0.5) + Interval::point(0.25);
+    assert_eq!(sum, Interval::point(0.75));
+    let product = Interval::new(-2.0, 3.0) * Interval::new(-1.0, 4.0);
+    assert_eq!(product, Interval::new(-8.0, 12.0));
+    let third = Interval::point(1.0) / Interval::point(3.0);
+    assert!(third.contains(1.0 / 3.0));
+    assert_eq!(third.upper(), third.lower().next_up());
+}
+
+#[test]
+fn interval_operations_on_wide_intervals() {
+    let a = Interval::new(-1.0, 2.0);
+    assert_eq!(a.abs(), Interval::new(0.0, 2.0));
+    assert_eq!(-a, Interval::new(-2.0, 1.0));
+    assert_eq!(Interval::point(1.0) / a, Interval::entire());
+    assert_eq!(
+        Interval::new(1.0, 2.0) / Interval::new(4.0, 8.0),
+        Interval::new(0.125, 0.5)
+    );
+    assert!(Interval::new(0.0, 1.0) < Interval::new(2.0, 3.0));
+    assert_eq!(
+        Interval::new(0.0, 2.0).partial_cmp(&Interval::new(1.0, 3.0)),
+        None
+    );
+    assert!(Interval::new(0.0, 3.0).contains_in_interior(&Interval::new(1.0, 2.0)));
+    assert!(!Interval::new(0.0, 3.0).contains_in_interior(&Interval::new(0.0, 2.0)));
+    assert_eq!(
+        Interval::point(f64::MAX) + Interval::point(f64::MAX),
+        Interval::new(f64::MAX, f64::INFINITY)
+    );
+    assert_eq!(Interval::new(1.0, 3.0).midpoint(), 2.0);
+    assert_eq!(Interval::epsilon().to_f64(), f64::EPSILON);
+}
+
+#[test]
+fn square_root_encloses_the_exact_root() {
+    let two = Interval::point(2.0).sqrt();
+    assert!(two.contains(std::f64::consts::SQRT_2));
+    assert!(two.width() > 0.0);
+    //sqrt(2)^2 = 2 must still be a member of the square.
+    assert!((two * two).contains(2.0));
+    //Negative members have no root and are dropped.
+    let straddling = Interval::new(-1.0, 4.0).sqrt();
+    assert_eq!(straddling.lower(), 0.0);
+    assert!(straddling.contains(2.0));
+}
@@ -0,0 +1,75 @@
+use matrix::count::{count_operations, Counted};
+use matrix::kernels::{add, dot_subtract, scale, subtract_multiple};
+
+///Returns `length` values between -1 and 1 from a linear congruential generator.
+fn pseudo_random(length: usize, seed: u64) -> Vec<f64> {
+    let mut state = seed;
+    (0..length)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+        })
+        .collect()
+}
+
+#[test]
+fn vectorized_row_operations_match_the_plain_loops() {
+    //Lengths on either side of whole vectors of f64 and f32 lanes, and slices that start part way into a row.
+    for length in 0..40 {
+        let target = pseudo_random(length, 1);
+        let source = pseudo_random(length, 2);
+        for offset in 0..length.min(3) {
+            let kernels: [fn(&mut [f64], bool); 3] = [
+                |values, simd| scale(values, &0.3, simd),
+                |values, simd| add(values, &-0.7, simd),
+                |values, simd| subtract_multiple(values, &1.9, &pseudo_random(40, 2), simd),
+            ];
+            for kernel in kernels {
+                let (mut plain, mut vectorized) = (target.clone(), target.clone());
+                kernel(&mut plain[offset..], false);
+                kernel(&mut vectorized[offset..], true);
+                assert_eq!(plain, vectorized, "length {}", length);
+            }
+
+            let single =
+                |values: &[f64]| -> Vec<f32> { values.iter().map(|v| *v as f32).collect() };
+            let (mut plain, mut vectorized) = (single(&target), single(&target));
+            subtract_multiple(&mut plain[offset..], &0.5, &single(&source), false);
+            subtract_multiple(&mut vectorized[offset..], &0.5, &single(&source), true);
+            assert_eq!(plain, vectorized, "length {}", length);
+        }
+    }
+}
+
+#[test]
+fn vectorized_dot_product_agrees_to_rounding() {
+    for length in 0..40 {
+        let (x, y) = (pseudo_random(length, 3), pseudo_random(length, 4));
+        let plain = dot_subtract(1.0, &x, &y, false);
+        let vectorized = dot_subtract(1.0, &x, &y, true);
+        let magnitude: f64 = 1.0 + x.iter().zip(&y).map(|(x, y)| (x * y).abs()).sum::<f64>();
+        assert!(
+            (plain - vectorized).abs() <= length as f64 * f64::EPSILON * magnitude,
+            "length {}",
+            length
+        );
+
+        let single = |values: &[f64]| -> Vec<f32> { values.iter().map(|v| *v as f32).collect() };
+        let plain = dot_subtract(1.0f32, &single(&x), &single(&y), false);
+        let vectorized = dot_subtract(1.0f32, &single(&x), &single(&y), true);
+        assert!((plain - vectorized).abs() <= length as f32 * f32::EPSILON * magnitude as f32);
+    }
+}
+
+#[test]
+fn types_without_vectorized_kernels_use_the_plain_loops() {
+    let x: Vec<Counted> = pseudo_random(9, 5).into_iter().map(Counted).collect();
+    let y: Vec<Counted> = pseudo_random(9, 6).into_iter().map(Counted).collect();
+    let (plain, plain_counts) = count_operations(|| dot_subtract(Counted(0.0), &x, &y, false));
+    let (vectorized, counts) = count_operations(|| dot_subtract(Counted(0.0), &x, &y, true));
+    assert_eq!(plain, vectorized);
+    assert_eq!(plain_counts, counts);
+    assert_eq!(counts.multiplications, 9);
+}
@@ -0,0 +1,242 @@
+use matrix::least_squares::{Error, LeastSquaresMethod, LeastSquaresOptions};
+use matrix::Matrix;
+
+const METHODS: [LeastSquaresMethod; 3] = [
+    LeastSquaresMethod::NormalEquations,
+    LeastSquaresMethod::Qr,
+    LeastSquaresMethod::Svd,
+];
+
+fn with_method(method: LeastSquaresMethod) -> LeastSquaresOptions {
+    LeastSquaresOptions {
+        method,
+        ..Default::default()
+    }
+}
+
+///A cubic in t on [-1, 1], sampled at `points` points, with a deterministic wobble added to y.
+fn cubic_fit(points: usize) -> (Matrix<f64>, Matrix<f64>) {
+    let t = |i: usize| -1.0 + 2.0 * i as f64 / (points - 1) as f64;
+    let a = Matrix::from(
+        (0..points)
+            .map(|i| (0..4).map(|power| t(i).powi(power)).collect())
+            .collect(),
+    );
+    let b = Matrix::from(
+        (0..points)
+            .map(|i| {
+                let t = t(i);
+                vec![0.5 - t + 2.0 * t * t + 0.25 * t * t * t + 0.01 * (7.0 * t).sin()]
+            })
+            .collect(),
+    );
+    (a, b)
+}
+
+#[test]
+fn every_method_finds_the_same_fit() {
+    let (a, b) = cubic_fit(25);
+    let fits: Vec<_> = METHODS
+        .iter()
+        .map(|method| a.least_squares_with(&b, with_method(*method)).unwrap())
+        .collect();
+    for fit in &fits {
+        assert_eq!(fit.rank, 4);
+        assert_eq!(fit.warning, None);
+        for row in 0..4 {
+            let difference = fit.solution.values[row][0] - fits[1].solution.values[row][0];
+            assert!(
+                difference.abs() < 1.0e-12,
+                "{:?}: {}",
+                fit.method,
+                difference
+            );
+        }
+        //The residual is orthogonal to the columns of A.
+        for column in 0..4 {
+            let mut dot = 0.0;
+            for row in 0..25 {
+                let fitted: f64 = (0..4)
+                    .map(|i| a.values[row][i] * fit.solution.values[i][0])
+                    .sum();
+                dot += a.values[row][column] * (b.values[row][0] - fitted);
+            }
+            assert!(dot.abs() < 1.0e-13, "{:?}: {}", fit.method, dot);
+        }
+        let relative = (fit.residual_sum_of_squares[0] - fits[2].residual_sum_of_squares[0])
+            / fits[2].residual_sum_of_squares[0];
+        assert!(relative.abs() < 1.0e-10);
+        let condition = (fit.condition - fits[2].condition) / fits[2].condition;
+        //The estimate is within a factor of sqrt(n) of the SVD's.
+        assert!(condition.abs() < 1.0, "{:?}: {}", fit.method, fit.condition);
+    }
+}
+
+#[test]
+fn covariance_of_a_straight_line() {
+    //For y = c + d * t, var(d) = s^2 / sum (t - mean)^2 and var(c) = s^2 * sum t^2 / (m * sum (t - mean)^2).
+    let t = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+    let y = [1.1, 2.9, 5.2, 6.8, 9.1, 11.0];
+    let a = Matrix::from(t.iter().map(|t| vec![1.0, *t]).collect());
+    let b = Matrix::from(y.iter().map(|y| vec![*y]).collect());
+    let mean = 2.5;
+    let spread: f64 = t.iter().map(|t| (t - mean) * (t - mean)).sum();
+    for method in METHODS {
+        let fit = a.least_squares_with(&b, with_method(method)).unwrap();
+        let (c, d) = (fit.solution.values[0][0], fit.solution.values[1][0]);
+        let rss: f64 = t.iter().zip(&y).map(|(t, y)| (y - c - d * t).powi(2)).sum();
+        assert!((fit.residual_sum_of_squares[0] - rss).abs() < 1.0e-12);
+        let variance = rss / 4.0;
+        let covariance = &fit.covariance.as_ref().unwrap()[0];
+        assert!((covariance.values[1][1] - variance / spread).abs() < 1.0e-12);
+        let sum_of_squares: f64 = t.iter().map(|t| t * t).sum();
+        assert!(
+            (covariance.values[0][0] - variance * sum_of_squares / (6.0 * spread)).abs() < 1.0e-12
+        );
+        assert!((covariance.values[0][1] - covariance.values[1][0]).abs() < 1.0e-14);
+        assert!((covariance.values[0][1] + variance * mean / spread).abs() < 1.0e-12);
+    }
+}
+
+#[test]
+fn square_systems_have_no_covariance() {
+    let a = Matrix::from(vec![vec![3.0, -2.0], vec![6.0, 4.0]]);
+    let b = Matrix::from(vec![vec![4.0, 1.0], vec![-8.0, 10.0]]);
+    for method in METHODS {
+        let fit = a.least_squares_with(&b, with_method(method)).unwrap();
+        assert_eq!(fit.covariance, None);
+        assert_eq!(fit.residual_sum_of_squares.len(), 2);
+        assert!((fit.solution.values[1][0] + 2.0f64).abs() < 1.0e-12);
+        assert!((fit.solution.values[1][1] - 1.0f64).abs() < 1.0e-12);
+    }
+}
+
+#[test]
+fn normal_equations_square_the_condition_number() {
+    //Lauchli's matrix: 1 + delta^2 rounds to 1, so A^T * A is singular in f64 while A is not.
+    let delta = 1.0e-8;
+    let a = Matrix::from(vec![vec![1.0, 1.0], vec![delta, 0.0], vec![0.0, delta]]);
+    let b = Matrix::from(vec![vec![2.0], vec![delta], vec![delta]]);
+    let normal = a.least_squares_with(&b, with_method(LeastSquaresMethod::NormalEquations));
+    assert_eq!(normal.unwrap_err(), Error::RankDeficient { column: 1 });
+    for method in [LeastSquaresMethod::Qr, LeastSquaresMethod::Svd] {
+        let fit = a.least_squares_with(&b, with_method(method)).unwrap();
+        assert_eq!(fit.warning, None);
+        assert!((fit.solution.values[0][0] - 1.0f64).abs() < 1.0e-7);
+        assert!((fit.solution.values[1][0] - 1.0f64).abs() < 1.0e-7);
+    }
+
+    //Less ill conditioned, the normal equations solve it, and warn.
+    let delta = 1.0e-5;
+    let a = Matrix::from(vec![vec![1.0, 1.0], vec![delta, 0.0], vec![0.0, delta]]);
+    let options = with_method(LeastSquaresMethod::NormalEquations);
+    let fit = a.least_squares_with(&b, options).unwrap();
+    let warning = fit.warning.unwrap();
+    assert_eq!(warning.condition, fit.condition);
+    assert!(warning.condition > 1.0e5);
+    assert_eq!(warning.squared, warning.condition * warning.condition);
+    assert_eq!(warning.limit, 1.0 / f64::EPSILON.sqrt());
+    let options = LeastSquaresOptions {
+        condition_limit: Some(1.0e12),
+        ..options
+    };
+    assert_eq!(a.least_squares_with(&b, options).unwrap().warning, None);
+}
+
+#[test]
+fn rank_deficient_columns() {
+    //The third column repeats the first.
+    let a = Matrix::from((0..5).map(|i| vec![i as f64, 1.0, i as f64]).collect());
+    let b = Matrix::from((0..5).map(|i| vec![1.0 + 4.0 * i as f64]).collect());
+    for method in [LeastSquaresMethod::NormalEquations, LeastSquaresMethod::Qr] {
+        let error = a.least_squares_with(&b, with_method(method)).unwrap_err();
+        assert_eq!(error, Error::RankDeficient { column: 2 });
+    }
+    //The SVD drops the zero singular value, and splits the slope evenly, the solution of least norm.
+    let fit = a
+        .least_squares_with(&b, with_method(LeastSquaresMethod::Svd))
+        .unwrap();
+    assert_eq!(fit.rank, 2);
+    let expected = [2.0, 1.0, 2.0];
+    for (row, expected) in expected.iter().enumerate() {
+        assert!((fit.solution.values[row][0] - expected).abs() < 1.0e-12);
+    }
+    assert!(fit.residual_sum_of_squares[0] < 1.0e-24);
+    assert!(fit.covariance.is_some());
+}
+
+#[test]
+fn least_squares_errors() {
+    let a = Matrix::from(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+    let b = Matrix::from(vec![vec![1.0], vec![2.0]]);
+    assert_eq!(
+        a.least_squares(&b).unwrap_err(),
+        Error::Underdetermined {
+            rows: 2,
+            columns: 3
+        }
+    );
+    let b = Matrix::from(vec![vec![1.0], vec![2.0], vec![3.0]]);
+    assert_eq!(
+        a.least_squares(&b).unwrap_err(),
+        Error::DimensionMismatch {
+            a_rows: 2,
+            b_rows: 3
+        }
+    );
+}
+
+#[test]
+fn square_roots_are_counted_and_exact_where_they_can_be() {
+    use matrix::count::{count_operations, Counted};
+    use matrix::rational::Rational;
+    use matrix::Scalar;
+    let (a, b) = cubic_fit(12);
+    let counted = |m: &Matrix<f64>| {
+        Matrix::from(
+            m.values
+                .iter()
+                .map(|row| row.iter().map(|v| Counted(*v)).collect())
+                .collect(),
+        )
+    };
+    let (fit, counts) = count_operations(|| counted(&a).least_squares(&counted(&b)).unwrap());
+    //Householder QR takes the norm of each of the four columns.
+    assert!(counts.square_roots >= 4, "{:?}", counts);
+    assert_eq!(fit.solution.values.len(), 4);
+
+    let ratio =
+        |numerator: i64, denominator: i64| Rational::from(numerator) / Rational::from(denominator);
+    assert_eq!(ratio(4, 9).sqrt(), ratio(2, 3));
+    assert_eq!(ratio(1 << 40, 25).sqrt(), ratio(1 << 20, 5));
+    //Not a perfect square: rounded through f64.
+    assert_eq!(ratio(2, 1).sqrt(), Rational::from_f64(2f64.sqrt()));
+}
+
+#[test]
+fn rational_normal_equations_have_no_condition_limit() {
+    use matrix::rational::Rational;
+    use matrix::Scalar;
+    //y = 1 + 2 t, exactly. The Cholesky factors of A^T * A = [[4, 6], [6, 14]] need the square root of 5,
+    //which rounds, so the fit is close but not exact.
+    let a = Matrix::from(
+        (0..4)
+            .map(|t| vec![Rational::from(1), Rational::from(t)])
+            .collect(),
+    );
+    let b = Matrix::from((0..4).map(|t| vec![Rational::from(1 + 2 * t)]).collect());
+    //The Jacobi rotations of the SVD never reach an epsilon of zero, so it is left out.
+    for method in [LeastSquaresMethod::NormalEquations, LeastSquaresMethod::Qr] {
+        let fit = a.least_squares_with(&b, with_method(method)).unwrap();
+        assert_eq!(fit.warning, None);
+        let intercept = fit.solution.values[0][0].to_f64();
+        let slope = fit.solution.values[1][0].to_f64();
+        assert!(
+            (intercept - 1.0).abs() < 1.0e-12,
+            "{:?}: {}",
+            method,
+            intercept
+        );
+        assert!((slope - 2.0).abs() < 1.0e-12, "{:?}: {}", method, slope);
+    }
+}
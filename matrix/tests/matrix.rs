@@ -1,7 +1,7 @@
 #[test]
 fn new_matrix() {
     use matrix::Matrix;
-    let matrix = Matrix::new(2, 2);
+    let matrix: Matrix = Matrix::new(2, 2);
     let assertion = vec![vec![0.0; 2]; 2];
     assert_eq!(*matrix.values(), assertion);
     assert_eq!(matrix.columns(), 2);
@@ -10,7 +10,7 @@ fn new_matrix() {
 #[test]
 fn square_matrix() {
     use matrix::Matrix;
-    let matrix = Matrix::square(2);
+    let matrix: Matrix = Matrix::square(2);
     let assertion = vec![vec![0.0; 2]; 2];
     assert_eq!(*matrix.values(), assertion);
     assert_eq!(matrix.columns(), 2);
@@ -19,7 +19,7 @@ fn square_matrix() {
 #[test]
 fn identity_matrix() {
     use matrix::Matrix;
-    let identity = Matrix::identity(3);
+    let identity: Matrix = Matrix::identity(3);
     assert_eq!(
         *identity.values(),
         vec![
@@ -114,3 +114,20 @@ fn return_valid_row_length() {
 
     assert_eq!(matrix.columns(), 4);
 }
+
+#[test]
+fn norm_one() {
+    use matrix::Matrix;
+    let matrix = Matrix::from(vec![vec![3.0, -2.0], vec![-6.0, 4.0]]);
+    assert_eq!(matrix.norm_one(), 9.0);
+}
+#[test]
+fn is_mangled() {
+    use matrix::Matrix;
+    let mut matrix: Matrix = Matrix::new(2, 2);
+    assert!(!matrix.is_mangled());
+    matrix.values[1].push(0.0);
+    assert!(matrix.is_mangled());
+    let empty: Matrix = Matrix::new(0, 3);
+    assert!(!empty.is_mangled());
+}
@@ -0,0 +1,59 @@
+use matrix::pivoting::{select_pivot, Pivoting};
+use matrix::Matrix;
+
+fn candidates() -> Matrix<f64> {
+    Matrix::from(vec![
+        vec![1.0, 5.0, 0.0],
+        vec![2.0, 3.0, 9.0],
+        vec![4.0, 6.0, 1.0],
+    ])
+}
+
+#[test]
+fn select_pivot_by_each_strategy() {
+    let a = candidates();
+    let all = [0, 1, 2];
+    //Row 0 is large relative to its largest element, 5.
+    let scales = [1.0 / 5.0, 1.0 / 9.0, 1.0 / 6.0];
+    let select = |pivoting| select_pivot(&a, &all, &all, pivoting, &scales);
+    assert_eq!(select(Pivoting::None), Some((0, 0)));
+    assert_eq!(select(Pivoting::Partial), Some((2, 0)));
+    assert_eq!(select(Pivoting::ScaledPartial), Some((2, 0)));
+    assert_eq!(select(Pivoting::Complete), Some((1, 2)));
+    //From column 0, 4 leads to 6 in row 2, which is the largest in column 1.
+    assert_eq!(select(Pivoting::Rook), Some((2, 1)));
+}
+
+#[test]
+fn select_pivot_among_remaining_candidates() {
+    let a = candidates();
+    let (rows, columns) = ([0, 1], [0, 1]);
+    let scales = [1.0 / 5.0, 1.0 / 9.0, 1.0 / 6.0];
+    let select = |pivoting| select_pivot(&a, &rows, &columns, pivoting, &scales);
+    assert_eq!(select(Pivoting::Partial), Some((1, 0)));
+    //1 / 5 against 2 / 9.
+    assert_eq!(select(Pivoting::ScaledPartial), Some((1, 0)));
+    assert_eq!(select(Pivoting::Complete), Some((0, 1)));
+    assert_eq!(select(Pivoting::Rook), Some((0, 1)));
+}
+
+#[test]
+fn select_pivot_returns_none_for_a_zero_pivot() {
+    let a = Matrix::from(vec![vec![0.0, 1.0], vec![0.0, 2.0]]);
+    assert_eq!(
+        select_pivot(&a, &[0, 1], &[0, 1], Pivoting::None, &[]),
+        None
+    );
+    assert_eq!(
+        select_pivot(&a, &[0, 1], &[0, 1], Pivoting::Partial, &[]),
+        None
+    );
+    assert_eq!(
+        select_pivot(&a, &[0, 1], &[0, 1], Pivoting::Complete, &[]),
+        Some((1, 1))
+    );
+    assert_eq!(
+        select_pivot(&a, &[0, 1], &[0, 1], Pivoting::Rook, &[]),
+        Some((1, 1))
+    );
+}
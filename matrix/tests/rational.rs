@@ -0,0 +1,144 @@
+use matrix::bigint::BigInt;
+use matrix::pivoting::{select_pivot, Pivoting};
+use matrix::rational::Rational;
+use matrix::{Field, Matrix, Scalar};
+
+#[test]
+fn big_integers_divide_and_print_exactly() {
+    let power = BigInt::from(1).shl(200);
+    assert_eq!(
+        power.to_string(),
+        "1606938044258990275541962092341162602522202993782792835301376"
+    );
+    let divisor = BigInt::from(-12_345_678_901i64);
+    let (quotient, remainder) = power.div_rem(&divisor);
+    assert_eq!(
+        quotient.clone() * divisor.clone() + remainder.clone(),
+        power
+    );
+    assert!(remainder.abs() < divisor.abs());
+    assert!(quotient.is_negative());
+    assert_eq!(power.shr(199), BigInt::from(2));
+    assert_eq!(BigInt::from(84).gcd(&BigInt::from(-36)), BigInt::from(12));
+    assert_eq!((BigInt::from(5) - BigInt::from(5)).to_string(), "0");
+}
+
+#[test]
+fn big_integers_divide_by_many_digits() {
+    //Builds a magnitude from base 2^32 digits, most significant first.
+    let from_digits = |digits: &[u32]| {
+        digits.iter().fold(BigInt::zero(), |value, digit| {
+            value.shl(32) + BigInt::from(*digit as u64)
+        })
+    };
+    //Divides exactly by a divisor whose first quotient digit is overestimated, so the divisor is added back.
+    let dividend = from_digits(&[0x7fff_ffff, 0x8000_0000, 0, 0]);
+    let divisor = from_digits(&[0x8000_0000, 0, 1]);
+    let (quotient, remainder) = dividend.div_rem(&divisor);
+    assert_eq!(
+        quotient.clone() * divisor.clone() + remainder.clone(),
+        dividend
+    );
+    assert!(remainder < divisor && !remainder.is_negative());
+    let mut state = 41u64;
+    for length in 2..12 {
+        let mut digits = |count: usize| {
+            let digits: Vec<u32> = (0..count)
+                .map(|_| {
+                    state = state
+                        .wrapping_mul(6364136223846793005)
+                        .wrapping_add(1442695040888963407);
+                    (state >> 32) as u32
+                })
+                .collect();
+            from_digits(&digits)
+        };
+        let (dividend, divisor) = (digits(2 * length + 1), digits(length));
+        let (quotient, remainder) = dividend.div_rem(&divisor);
+        assert_eq!(
+            quotient.clone() * divisor.clone() + remainder.clone(),
+            dividend
+        );
+        assert!(remainder < divisor && !remainder.is_negative());
+    }
+}
+
+#[test]
+fn rationals_stay_in_lowest_terms() {
+    let a = Rational::new(2, 6);
+    assert_eq!(a, Rational::new(-1, -3));
+    assert_eq!(a.to_string(), "1/3");
+    assert_eq!(
+        Rational::new(1, 6) - Rational::new(1, 2),
+        Rational::new(-1, 3)
+    );
+    assert_eq!(
+        Rational::new(2, 3) * Rational::new(9, 4),
+        Rational::new(3, 2)
+    );
+    assert_eq!(
+        Rational::new(2, 3) / Rational::new(-4, 9),
+        Rational::new(-3, 2)
+    );
+    assert!(Rational::new(-1, 2) < Rational::new(1, 3));
+    assert_eq!(Rational::new(-7, 3).abs(), Rational::new(7, 3));
+    assert!(Rational::new(0, 5).is_integer());
+}
+
+#[test]
+fn rationals_convert_to_and_from_f64() {
+    for value in [0.1, -2.5, 1e300, 5e-324, 3.0] {
+        assert_eq!(Rational::from_f64(value).to_f64(), value);
+    }
+    assert_eq!(
+        Rational::from_f64(0.1).denominator(),
+        &BigInt::from(1).shl(55)
+    );
+    assert!((Rational::new(1, 3).to_f64() - 1.0 / 3.0).abs() < 1e-17);
+    assert_eq!(Rational::epsilon(), Rational::zero());
+}
+
+#[test]
+#[should_panic(expected = "Division by zero.")]
+fn rational_division_by_zero_panics() {
+    let _ = Rational::from(1) / Rational::zero();
+}
+
+#[test]
+fn exact_types_pivot_on_the_first_non_zero_candidate() {
+    let values = [[0, 7, 1], [1, 2, 9], [3, 4, 5]];
+    let exact = Matrix::from(
+        values
+            .iter()
+            .map(|row| row.iter().map(|v| Rational::from(*v as i64)).collect())
+            .collect(),
+    );
+    let float = Matrix::from(
+        values
+            .iter()
+            .map(|row| row.iter().map(|v| *v as f64).collect())
+            .collect(),
+    );
+    let (rows, scales) = (
+        [0, 1, 2],
+        [Rational::one(), Rational::one(), Rational::one()],
+    );
+    for pivoting in [Pivoting::None, Pivoting::Partial, Pivoting::ScaledPartial] {
+        assert_eq!(
+            select_pivot(&exact, &rows, &rows, pivoting, &scales),
+            Some((1, 0))
+        );
+    }
+    assert_eq!(
+        select_pivot(&exact, &[0], &rows, Pivoting::Complete, &scales),
+        Some((0, 1))
+    );
+    assert_eq!(
+        select_pivot(&float, &rows, &rows, Pivoting::Partial, &[1.0; 3]),
+        Some((2, 0))
+    );
+    assert_eq!(
+        select_pivot(&float, &rows, &rows, Pivoting::FirstNonZero, &[1.0; 3]),
+        Some((1, 0))
+    );
+}
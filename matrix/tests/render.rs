@@ -0,0 +1,65 @@
+fn recorded_elimination() -> matrix::trace::Recorder {
+    use matrix::trace::{Recorder, Step, Trace};
+    use matrix::Matrix;
+    let mut a = Matrix::from(vec![vec![1.0, 2.0], vec![2.0, 1.0]]);
+    let mut b = Matrix::from(vec![vec![5.0], vec![4.0]]);
+    let mut recorder = Recorder::new();
+    recorder.start(&a, Some(&b));
+    recorder.record(Step::SelectPivot { row: 1, column: 0 }, &a, Some(&b));
+    a.swap_rows((0, 1));
+    b.swap_rows((0, 1));
+    recorder.record(Step::SwapRows { rows: (1, 0) }, &a, Some(&b));
+    a.scale_row(0, 0.5);
+    b.scale_row(0, 0.5);
+    recorder.record(Step::ScaleRow { row: 0, scale: 0.5 }, &a, Some(&b));
+    a.values[1] = vec![0.0, 1.5];
+    b.values[1] = vec![3.0];
+    recorder.record(
+        Step::EliminateRow {
+            target: 1,
+            source: 0,
+            factor: 1.0,
+        },
+        &a,
+        Some(&b),
+    );
+    recorder
+}
+
+#[test]
+fn latex_worked_example() {
+    use matrix::render;
+    let latex = render::latex(recorded_elimination().frames());
+    let expected_swap = [
+        r"% Step 2",
+        r"\[",
+        r"\begin{bmatrix} 1 & 2 \\ \boxed{2} & 1 \end{bmatrix} \;\Big|\; \begin{bmatrix} 5 \\ 4 \end{bmatrix}",
+        r"\xrightarrow{R_{2} \leftrightarrow R_{1}}",
+        r"\begin{bmatrix} \boxed{2} & 1 \\ 1 & 2 \end{bmatrix} \;\Big|\; \begin{bmatrix} 4 \\ 5 \end{bmatrix}",
+        r"\]",
+    ]
+    .join("\n");
+    assert!(latex.contains(&expected_swap), "{}", latex);
+    assert!(latex.contains(r"\xrightarrow{R_{1} \leftarrow 0.5 R_{1}}"));
+    assert!(latex.contains(r"\xrightarrow{R_{2} \leftarrow R_{2} - 1 R_{1}}"));
+    assert_eq!(latex.matches("% Step").count(), 4);
+}
+
+#[test]
+fn markdown_worked_example() {
+    use matrix::render;
+    let markdown = render::markdown(recorded_elimination().frames());
+    let expected_scale = "### Step 3: R1 ← 0.5R1\n\n\
+        Before:\n\n\
+        |  | C1 | C2 | b1 |\n\
+        |---|---|---|---|\n\
+        | R1 | **2** | 1 | 4 |\n\
+        | R2 | 1 | 2 | 5 |\n\
+        \nAfter:\n\n\
+        |  | C1 | C2 | b1 |\n\
+        |---|---|---|---|\n\
+        | R1 | **1** | 0.5 | 2 |\n\
+        | R2 | 1 | 2 | 5 |\n";
+    assert!(markdown.contains(expected_scale), "{}", markdown);
+    assert!(markdown.contains("### Step 4: R2 ← R2 − 1R1"));
+}
@@ -0,0 +1,56 @@
+use matrix::verify::verify;
+use matrix::Matrix;
+
+#[test]
+fn verify_exact_solution() {
+    let a = Matrix::from(vec![vec![3.0, -2.0], vec![6.0, 4.0]]);
+    let x = Matrix::from(vec![vec![0.0, 1.0], vec![-2.0, 1.0]]);
+    let b = Matrix::from(vec![vec![4.0, 1.0], vec![-8.0, 10.0]]);
+    let verification = verify(&a, &x, &b, None);
+    assert_eq!(verification.residual, Matrix::new(2, 2));
+    assert_eq!(verification.normwise_backward_error, 0.0);
+    assert_eq!(verification.componentwise_backward_error, 0.0);
+    assert_eq!(verification.forward_error_bound, None);
+    assert!(verification.is_acceptable(0.0));
+}
+
+#[test]
+fn verify_perturbed_solution() {
+    let a = Matrix::from(vec![vec![2.0, 0.0], vec![0.0, 4.0]]);
+    let x = Matrix::from(vec![vec![1.0], vec![1.5]]);
+    let b = Matrix::from(vec![vec![2.0], vec![4.0]]);
+    let verification = verify(&a, &x, &b, Some(0.5));
+    assert_eq!(
+        verification.residual,
+        Matrix::from(vec![vec![0.0], vec![-2.0]])
+    );
+    //||r|| / (||A|| * ||x|| + ||b||) = 2 / (4 * 2.5 + 6)
+    assert_eq!(verification.normwise_backward_error, 2.0 / 16.0);
+    //|r| / (|A| * |x| + |b|) in the second row = 2 / (6 + 4)
+    assert_eq!(verification.componentwise_backward_error, 2.0 / 10.0);
+    //cond(A) * e = 2 / 8
+    assert_eq!(verification.forward_error_bound, Some(2.0 * 0.25 / 0.75));
+    assert!(!verification.is_acceptable(0.1));
+}
+
+#[test]
+fn verify_forward_error_bound_needs_a_well_conditioned_problem() {
+    let a = Matrix::from(vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+    let x = Matrix::from(vec![vec![0.0], vec![0.0]]);
+    let b = Matrix::from(vec![vec![1.0], vec![1.0]]);
+    let verification = verify(&a, &x, &b, Some(1.0));
+    assert_eq!(verification.normwise_backward_error, 1.0);
+    assert_eq!(verification.forward_error_bound, None);
+}
+
+#[test]
+fn verify_componentwise_error_catches_badly_scaled_rows() {
+    let a: Matrix<f64> = Matrix::from(vec![vec![1.0e6, 0.0], vec![0.0, 1.0e-6]]);
+    let x = Matrix::from(vec![vec![1.0], vec![2.0]]);
+    let b = Matrix::from(vec![vec![1.0e6], vec![1.0e-6]]);
+    let verification = verify(&a, &x, &b, None);
+    assert!(verification.normwise_backward_error < 1e-12);
+    //|r| / (|A| * |x| + |b|) = 1e-6 / 3e-6 in the second row.
+    assert!((verification.componentwise_backward_error - 1.0 / 3.0).abs() < 1e-12);
+    assert!(!verification.is_acceptable(1e-6));
+}